@@ -21,6 +21,8 @@ pub struct ApiErrorBuilder {
 pub enum ApiErrorExtension {
     #[serde(rename_all = "camelCase")]
     WadHashtablesMissing,
+    #[serde(rename_all = "camelCase")]
+    BinHashtablesMissing,
 }
 
 impl ApiError {
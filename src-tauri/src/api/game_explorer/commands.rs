@@ -1,12 +1,22 @@
 use crate::{
-    api::error::ApiError,
-    core::wad::tree::{WadTreeItem, WadTreePathable},
-    state::{GameExplorer, GameExplorerState, MergedItemRef, SettingsState, WadHashtableState},
+    api::{error::ApiError, hashtable::ExportUnresolvedHashesResponse, wad::HashCoverageDto},
+    core::wad::{
+        self,
+        tree::{WadTreeItem, WadTreePathable},
+    },
+    paths::MOD_OVERLAYS_DIR,
+    state::{
+        GameExplorer, GameExplorerState, MergedItemRef, ModOverlay, SettingsState,
+        WadHashtableState,
+    },
+    utils::actions::emit_action_progress,
 };
-use camino::Utf8Path;
-use color_eyre::eyre::eyre;
+use camino::{Utf8Path, Utf8PathBuf};
+use color_eyre::eyre::{Context, ContextCompat, eyre};
 use league_toolkit::file::LeagueFileKind;
 use serde::Serialize;
+use std::{fs::File, io::BufWriter};
+use tauri::Manager as _;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize)]
@@ -15,6 +25,16 @@ pub struct GameExplorerStatusResponse {
     pub is_initialized: bool,
     pub wad_count: usize,
     pub base_path: Option<String>,
+    pub coverage: HashCoverageDto,
+}
+
+/// Resolution coverage of the WADs inside of a directory of the league directory
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameExplorerDirectoryCoverageDto {
+    pub path: String,
+    pub wad_count: usize,
+    pub coverage: HashCoverageDto,
 }
 
 #[derive(Debug, Serialize)]
@@ -38,6 +58,10 @@ pub enum GameExplorerItemDto {
         extension_kind: LeagueFileKind,
         compressed_size: u64,
         uncompressed_size: u64,
+        /// Whether a mod overlay replaces this file
+        is_overridden: bool,
+        /// The mod overlay this file comes from
+        mod_overlay_id: Option<Uuid>,
     },
     #[serde(rename = "directory", rename_all = "camelCase")]
     Directory {
@@ -54,7 +78,11 @@ fn item_ref_to_dto(
     game_explorer: &GameExplorer,
 ) -> Option<GameExplorerItemDto> {
     match item_ref {
-        MergedItemRef::File { wad_id, item_id } => {
+        MergedItemRef::File {
+            wad_id,
+            item_id,
+            is_overridden,
+        } => {
             // Look up the actual file from the WadTree
             let tree = game_explorer.wad_trees().get(wad_id)?;
             let item = tree.item_storage().get(item_id)?;
@@ -83,6 +111,10 @@ fn item_ref_to_dto(
                     extension_kind,
                     compressed_size: chunk.compressed_size() as u64,
                     uncompressed_size: chunk.uncompressed_size() as u64,
+                    is_overridden: *is_overridden,
+                    mod_overlay_id: game_explorer
+                        .get_wad_mod_overlay(wad_id)
+                        .map(|overlay| overlay.id),
                 })
             } else {
                 None
@@ -114,9 +146,29 @@ pub async fn get_game_explorer_status(
         is_initialized: game_explorer_guard.is_initialized(),
         wad_count: game_explorer_guard.wad_count(),
         base_path: settings_guard.league_directory.clone(),
+        coverage: game_explorer_guard.coverage().into(),
     })
 }
 
+#[tauri::command]
+pub async fn get_game_explorer_coverage(
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> Result<Vec<GameExplorerDirectoryCoverageDto>, ApiError> {
+    let game_explorer_guard = game_explorer.0.lock();
+
+    Ok(game_explorer_guard
+        .coverage_by_directory()
+        .into_iter()
+        .map(
+            |(path, (wad_count, coverage))| GameExplorerDirectoryCoverageDto {
+                path: path.into_string(),
+                wad_count,
+                coverage: coverage.into(),
+            },
+        )
+        .collect())
+}
+
 #[tauri::command]
 pub async fn mount_game_explorer(
     settings: tauri::State<'_, SettingsState>,
@@ -259,3 +311,171 @@ pub struct GameExplorerPathComponentDto {
     pub name: String,
     pub path: String,
 }
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModOverlayDto {
+    pub id: Uuid,
+    pub name: String,
+    pub path: String,
+    pub wad_count: usize,
+}
+
+impl From<&ModOverlay> for ModOverlayDto {
+    fn from(value: &ModOverlay) -> Self {
+        Self {
+            id: value.id,
+            name: value.name.to_string(),
+            path: value.path.to_string(),
+            wad_count: value.wad_ids.len(),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn mount_game_explorer_mod_overlay(
+    app: tauri::AppHandle,
+    mod_path: String,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> Result<ModOverlayDto, ApiError> {
+    let overlays_directory = app
+        .path()
+        .app_data_dir()
+        .map_err(|_| ApiError::from_message("failed to get app data dir"))?
+        .join(MOD_OVERLAYS_DIR);
+    let overlays_directory = Utf8PathBuf::from_path_buf(overlays_directory)
+        .map_err(|_| ApiError::from_message("app data dir is not valid utf-8"))?;
+
+    let hashtable = wad_hashtable.0.lock();
+    let mut game_explorer_guard = game_explorer.0.lock();
+
+    if !game_explorer_guard.is_initialized() {
+        return Err(eyre!("Game explorer not initialized"))?;
+    }
+
+    let overlay_id = game_explorer_guard.mount_mod_overlay(
+        Utf8Path::new(&mod_path),
+        &overlays_directory,
+        &hashtable,
+    )?;
+
+    let overlay = game_explorer_guard
+        .mod_overlays()
+        .get(&overlay_id)
+        .wrap_err("failed to find mounted mod overlay")?;
+
+    Ok(ModOverlayDto::from(overlay))
+}
+
+#[tauri::command]
+pub async fn unmount_game_explorer_mod_overlay(
+    overlay_id: Uuid,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> Result<(), ApiError> {
+    game_explorer.0.lock().unmount_mod_overlay(overlay_id);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_game_explorer_mod_overlays(
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> Result<Vec<ModOverlayDto>, ApiError> {
+    Ok(game_explorer
+        .0
+        .lock()
+        .mod_overlays()
+        .values()
+        .map(ModOverlayDto::from)
+        .collect())
+}
+
+/// Get every version of a file (base game and mod overlays), so they can be compared
+#[tauri::command]
+pub async fn get_game_explorer_item_layers(
+    wad_id: Uuid,
+    item_id: Uuid,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> Result<Vec<GameExplorerItemDto>, ApiError> {
+    let game_explorer_guard = game_explorer.0.lock();
+
+    let index = game_explorer_guard
+        .merged_index()
+        .ok_or_else(|| eyre!("Merged index not available"))?;
+
+    let Some(WadTreeItem::File(file)) = game_explorer_guard
+        .wad_trees()
+        .get(&wad_id)
+        .and_then(|tree| tree.item_storage().get(&item_id))
+    else {
+        return Err(eyre!("File not found: {}:{}", wad_id, item_id))?;
+    };
+
+    let file_refs = index.get_file_refs(file.chunk().path_hash());
+    let is_modded = file_refs
+        .iter()
+        .any(|(wad_id, _)| game_explorer_guard.get_wad_mod_overlay(wad_id).is_some());
+
+    // overlay files come after the base game files, only the topmost one is not overridden
+    Ok(file_refs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (wad_id, item_id))| {
+            let is_overridden = is_modded && i + 1 < file_refs.len();
+
+            item_ref_to_dto(
+                &MergedItemRef::File {
+                    wad_id: *wad_id,
+                    item_id: *item_id,
+                    is_overridden,
+                },
+                &game_explorer_guard,
+            )
+        })
+        .collect())
+}
+
+/// Write the hashes of every chunk in the game explorer whose path isn't known to `output_path`
+#[tauri::command]
+pub async fn export_game_explorer_unresolved_hashes(
+    app: tauri::AppHandle,
+    action_id: Uuid,
+    output_path: String,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> Result<ExportUnresolvedHashesResponse, ApiError> {
+    let hashtable = wad_hashtable.0.lock();
+    let mut game_explorer_guard = game_explorer.0.lock();
+
+    if !game_explorer_guard.is_initialized() {
+        return Err(eyre!("Game explorer is not initialized"))?;
+    }
+
+    let unresolved_chunks =
+        game_explorer_guard.collect_unresolved_chunks(&hashtable, |progress| {
+            emit_action_progress(
+                &app,
+                action_id,
+                progress,
+                Some("Identifying unresolved chunks...".into()),
+            )
+        })?;
+
+    let mut writer = BufWriter::new(
+        File::create(&output_path).wrap_err(format!("failed to create file: {}", output_path))?,
+    );
+    wad::write_unresolved_chunks(&mut writer, &unresolved_chunks).wrap_err(format!(
+        "failed to write unresolved hashes: {}",
+        output_path
+    ))?;
+
+    tracing::info!(
+        "exported game explorer unresolved hashes (chunk_count = {})",
+        unresolved_chunks.len()
+    );
+
+    Ok(ExportUnresolvedHashesResponse {
+        chunk_count: unresolved_chunks.len(),
+    })
+}
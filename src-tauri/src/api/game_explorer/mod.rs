@@ -4,7 +4,7 @@ pub use commands::*;
 
 // Re-export types
 pub use commands::{
-    GameExplorerItemDto, GameExplorerPathComponentDto, GameExplorerStatusResponse,
-    MountGameExplorerResponse,
+    GameExplorerDirectoryCoverageDto, GameExplorerItemDto, GameExplorerPathComponentDto,
+    GameExplorerStatusResponse, ModOverlayDto, MountGameExplorerResponse,
 };
 
@@ -1,13 +1,30 @@
 use crate::api::error::{ApiErrorBuilder, ApiErrorExtension};
 use crate::api::ApiResult;
+use crate::core::hashes::{
+    self, DEFAULT_WAD_HASHTABLES_BASE_URL, HASHTABLE_CACHE_DIR, HASHTABLE_DOWNLOAD_DIR,
+    HashDiscovery,
+};
+use crate::core::wad::{self, tree::WadTreePathable};
+use crate::paths::{
+    BIN_HASHTABLES_DIR, DISCOVERED_WAD_HASHTABLE_FILE, WAD_HASHTABLES_DIR,
+    WAD_HASHTABLES_MANIFEST_FILE,
+};
+use crate::state::{BinHashKind, BinHashtableState, WadHashtable};
+use crate::state::{GameExplorerState, MountedWadsState, SettingsState};
+use crate::utils::actions::emit_action_progress;
 use crate::{api::error::ApiError, state::WadHashtableState};
-use color_eyre::eyre::{self, Context};
-use octocrab::models::repos::ContentItems;
+use color_eyre::eyre::{self, Context, eyre};
+use std::collections::HashSet;
 use tauri::Manager as _;
 use tracing::info;
+use uuid::Uuid;
 use walkdir::WalkDir;
 
-use super::WadHashtableStatus;
+use super::{
+    BinHashNameDto, BinHashtableStatus, DiscoverWadHashesResponse, DiscoveredWadPathDto,
+    HashLocationDto, HashPathResponse, LookupHashResponse, UpdateWadHashtablesResponse,
+    WadHashtableOverrideDto, WadHashtableStatus,
+};
 
 #[tauri::command]
 pub async fn get_wad_hashtable_status(
@@ -20,6 +37,8 @@ pub async fn get_wad_hashtable_status(
 pub async fn load_wad_hashtables(
     app: tauri::AppHandle,
     wad_hashtable: tauri::State<'_, WadHashtableState>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    game_explorer: tauri::State<'_, GameExplorerState>,
 ) -> std::result::Result<(), ApiError> {
     info!("loading wad hashtables");
 
@@ -29,11 +48,14 @@ pub async fn load_wad_hashtables(
         .map_err(|_| ApiError::from_message("failed to get app data dir"))?
         .join("wad_hashtables");
 
-    if wad_hashtables_dir
+    // the cache and download directories alone don't count as having hashtables
+    if !wad_hashtables_dir
         .read_dir()
         .wrap_err("failed to read dir")?
-        .next()
-        .is_none()
+        .filter_map(|entry| entry.ok())
+        .any(|entry| {
+            entry.file_name() != HASHTABLE_CACHE_DIR && entry.file_name() != HASHTABLE_DOWNLOAD_DIR
+        })
     {
         return Err(ApiErrorBuilder::new()
             .message("Wad hashtables missing")
@@ -41,20 +63,454 @@ pub async fn load_wad_hashtables(
             .build());
     }
 
-    wad_hashtable.0.lock().items_mut().clear();
-    wad_hashtable.0.lock().add_from_dir(wad_hashtables_dir)?;
+    {
+        let mut wad_hashtable = wad_hashtable.0.lock();
+        wad_hashtable.clear();
+        wad_hashtable.add_from_dir(wad_hashtables_dir)?;
+    }
+
+    rebuild_wad_trees(&wad_hashtable, &mounted_wads, &game_explorer, None)?;
 
     Ok(())
 }
 
-async fn get_file_content(path: impl AsRef<str>) -> eyre::Result<ContentItems> {
-    let path = path.as_ref();
-    info!("getting github file content: {}", path);
+#[tauri::command]
+pub async fn get_bin_hashtable_status(
+    bin_hashtable: tauri::State<'_, BinHashtableState>,
+) -> ApiResult<BinHashtableStatus> {
+    Ok(bin_hashtable.0.lock().status())
+}
+
+#[tauri::command]
+pub async fn load_bin_hashtables(
+    app: tauri::AppHandle,
+    bin_hashtable: tauri::State<'_, BinHashtableState>,
+) -> ApiResult<BinHashtableStatus> {
+    info!("loading bin hashtables");
+
+    let bin_hashtables_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|_| ApiError::from_message("failed to get app data dir"))?
+        .join(BIN_HASHTABLES_DIR);
+
+    if !BinHashKind::ALL
+        .iter()
+        .any(|kind| bin_hashtables_dir.join(kind.file_name()).is_file())
+    {
+        return Err(ApiErrorBuilder::new()
+            .message("Bin hashtables missing")
+            .extend(ApiErrorExtension::BinHashtablesMissing)
+            .build());
+    }
+
+    let mut bin_hashtable = bin_hashtable.0.lock();
+    bin_hashtable.clear();
+    bin_hashtable.add_from_dir(bin_hashtables_dir)?;
+
+    Ok(bin_hashtable.status())
+}
+
+#[tauri::command]
+pub async fn update_wad_hashtables(
+    app: tauri::AppHandle,
+    action_id: Uuid,
+    settings: tauri::State<'_, SettingsState>,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> ApiResult<UpdateWadHashtablesResponse> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|_| ApiError::from_message("failed to get app data dir"))?;
+    let wad_hashtables_dir = app_data_dir.join(WAD_HASHTABLES_DIR);
+
+    let base_url = settings
+        .0
+        .read()
+        .wad_hashtables_base_url
+        .clone()
+        .filter(|base_url| !base_url.is_empty())
+        .unwrap_or(DEFAULT_WAD_HASHTABLES_BASE_URL.to_string());
+
+    let result = hashes::update_wad_hashtables(
+        &base_url,
+        &wad_hashtables_dir,
+        app_data_dir.join(WAD_HASHTABLES_MANIFEST_FILE),
+        |progress, message| {
+            emit_action_progress(
+                &app,
+                action_id,
+                progress,
+                message.map(|x| format!("Downloading {}...", x)),
+            )
+        },
+    )
+    .await
+    .wrap_err(format!("failed to update wad hashtables from {}", base_url))?;
+
+    if !result.updated_files.is_empty() || !wad_hashtable.0.lock().status().is_loaded {
+        emit_action_progress(&app, action_id, 1.0, Some("Loading hashtables...".into()))?;
+
+        {
+            let mut wad_hashtable = wad_hashtable.0.lock();
+            wad_hashtable.clear();
+            wad_hashtable.add_from_dir(wad_hashtables_dir)?;
+        }
+
+        rebuild_wad_trees(&wad_hashtable, &mounted_wads, &game_explorer, None)?;
+    }
+
+    Ok(UpdateWadHashtablesResponse {
+        updated_files: result.updated_files,
+        unchanged_files: result.unchanged_files,
+    })
+}
+
+/// Mine the contents of the mounted wads for the paths of their unresolved chunks
+///
+/// Discovered paths are appended to the discovered hashtable and applied right away.
+#[tauri::command]
+pub async fn discover_wad_hashes(
+    app: tauri::AppHandle,
+    action_id: Uuid,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> ApiResult<DiscoverWadHashesResponse> {
+    let wad_hashtables_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|_| ApiError::from_message("failed to get app data dir"))?
+        .join(WAD_HASHTABLES_DIR);
+
+    let (discovered, scanned_chunk_count, unresolved_count) = {
+        let mut mounted_wads = mounted_wads.0.lock();
+
+        let unresolved = {
+            let wad_hashtable = wad_hashtable.0.lock();
+            mounted_wads
+                .wads()
+                .values()
+                .flat_map(|wad| wad.chunks().keys())
+                .filter(|path_hash| wad_hashtable.get_path(**path_hash).is_none())
+                .copied()
+                .collect::<HashSet<_>>()
+        };
+
+        info!(
+            "discovering wad hashes (unresolved_count: {})",
+            unresolved.len()
+        );
+
+        let mut discovery = HashDiscovery::new(unresolved);
+        let mut scanned_chunk_count = 0;
+
+        let wad_count = mounted_wads.wads().len();
+        for (i, wad) in mounted_wads.wads_mut().values_mut().enumerate() {
+            let (mut decoder, chunks) = wad.decode();
+            scanned_chunk_count += discovery.scan_wad_chunks(&mut decoder, chunks, |progress| {
+                emit_action_progress(
+                    &app,
+                    action_id,
+                    (i as f64 + progress) / wad_count as f64,
+                    Some(format!("Scanning wad {} of {}...", i + 1, wad_count)),
+                )
+            })?;
+        }
+
+        let unresolved_count = discovery.unresolved_count();
+        (
+            discovery.into_discovered(),
+            scanned_chunk_count,
+            unresolved_count,
+        )
+    };
+
+    info!(
+        "discovered wad hashes (discovered_count: {}, unresolved_count: {})",
+        discovered.len(),
+        unresolved_count
+    );
 
-    Ok(octocrab::instance()
-        .repos("CommunityDragon", "CDTB")
-        .get_content()
-        .path(path)
-        .send()
-        .await?)
+    if !discovered.is_empty() {
+        hashes::append_discovered_paths(
+            wad_hashtables_dir.join(DISCOVERED_WAD_HASHTABLE_FILE),
+            &discovered,
+        )
+        .wrap_err("failed to save discovered paths")?;
+
+        {
+            let mut wad_hashtable = wad_hashtable.0.lock();
+            for (path_hash, path) in &discovered {
+                wad_hashtable.insert(*path_hash, path.as_str());
+            }
+        }
+
+        rebuild_wad_trees(
+            &wad_hashtable,
+            &mounted_wads,
+            &game_explorer,
+            Some(&discovered.keys().copied().collect()),
+        )?;
+    }
+
+    Ok(DiscoverWadHashesResponse {
+        scanned_chunk_count,
+        unresolved_count,
+        discovered_paths: discovered
+            .into_iter()
+            .map(|(path_hash, path)| DiscoveredWadPathDto {
+                path_hash: format!("{:x}", path_hash),
+                path,
+            })
+            .collect(),
+    })
+}
+
+#[tauri::command]
+pub async fn get_wad_hashtable_overrides(
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+) -> ApiResult<Vec<WadHashtableOverrideDto>> {
+    let wad_hashtable = wad_hashtable.0.lock();
+
+    let mut overrides = wad_hashtable
+        .overrides()
+        .iter()
+        .map(|(path_hash, path)| (*path_hash, path.clone()))
+        .collect::<Vec<_>>();
+    overrides.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+    Ok(overrides
+        .into_iter()
+        .map(|(path_hash, path)| WadHashtableOverrideDto {
+            path_hash: format!("{:x}", path_hash),
+            path: path.to_string(),
+        })
+        .collect())
+}
+
+/// Assign a path to a hash, it takes priority over every other hashtable
+#[tauri::command]
+pub async fn add_wad_hashtable_override(
+    app: tauri::AppHandle,
+    path_hash: String,
+    path: String,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> ApiResult<()> {
+    let path_hash = parse_path_hash(&path_hash)?;
+    let path = path.trim().replace('\\', "/");
+    if path.is_empty() {
+        return Err(ApiError::from_message("path is empty"));
+    }
+    // the user hashtable would read it back as a comment
+    if path.contains(" #") {
+        return Err(ApiError::from_message("path must not contain \" #\""));
+    }
+
+    info!(
+        "adding wad hashtable override (path_hash: {:x}): {}",
+        path_hash, path
+    );
+
+    update_wad_hashtable_overrides(&app, &wad_hashtable, |wad_hashtable| {
+        wad_hashtable.set_override(path_hash, path)
+    })?;
+
+    rebuild_wad_trees(
+        &wad_hashtable,
+        &mounted_wads,
+        &game_explorer,
+        Some(&HashSet::from([path_hash])),
+    )?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn remove_wad_hashtable_override(
+    app: tauri::AppHandle,
+    path_hash: String,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> ApiResult<()> {
+    let path_hash = parse_path_hash(&path_hash)?;
+
+    info!(
+        "removing wad hashtable override (path_hash: {:x})",
+        path_hash
+    );
+
+    update_wad_hashtable_overrides(&app, &wad_hashtable, |wad_hashtable| {
+        wad_hashtable.remove_override(path_hash);
+    })?;
+
+    rebuild_wad_trees(
+        &wad_hashtable,
+        &mounted_wads,
+        &game_explorer,
+        Some(&HashSet::from([path_hash])),
+    )?;
+
+    Ok(())
+}
+
+/// Hash a path with both the wad path hash and the BIN name hash
+#[tauri::command]
+pub async fn hash_path(path: String) -> ApiResult<HashPathResponse> {
+    Ok(HashPathResponse {
+        wad_path_hash: format!("{:016x}", wad::hash_chunk_path(&path)),
+        bin_hash: format!("{:08x}", hashes::hash_bin_name(&path)),
+    })
+}
+
+/// Look up a hash in the loaded hashtables and find the mounted chunks with that hash
+#[tauri::command]
+pub async fn lookup_hash(
+    hash: String,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    bin_hashtable: tauri::State<'_, BinHashtableState>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    game_explorer: tauri::State<'_, GameExplorerState>,
+) -> ApiResult<LookupHashResponse> {
+    let hash = parse_path_hash(&hash)?;
+
+    let wad_path = wad_hashtable.0.lock().get_path(hash);
+
+    // BIN hashes are 32-bit, so wider hashes can only be wad path hashes
+    let bin_names = match u32::try_from(hash) {
+        Ok(bin_hash) => {
+            let bin_hashtable = bin_hashtable.0.lock();
+            BinHashKind::ALL
+                .into_iter()
+                .filter_map(|kind| {
+                    bin_hashtable
+                        .get(kind, bin_hash)
+                        .map(|name| BinHashNameDto {
+                            kind,
+                            name: name.to_string(),
+                        })
+                })
+                .collect()
+        }
+        Err(_) => Vec::new(),
+    };
+
+    let mounted_wad_items = {
+        let mounted_wads = mounted_wads.0.lock();
+        mounted_wads
+            .wad_trees()
+            .iter()
+            .filter(|(wad_id, _)| {
+                mounted_wads
+                    .wads()
+                    .get(wad_id)
+                    .is_some_and(|wad| wad.chunks().contains_key(&hash))
+            })
+            .filter_map(|(wad_id, wad_tree)| {
+                let file = wad_tree.find_file(hash)?;
+                Some(HashLocationDto {
+                    wad_id: *wad_id,
+                    wad_name: wad_tree.wad_path().to_string(),
+                    item_id: file.id(),
+                    path: file.path().to_string(),
+                })
+            })
+            .collect()
+    };
+
+    let game_explorer_items = {
+        let game_explorer = game_explorer.0.lock();
+        match game_explorer.merged_index() {
+            Some(merged_index) => merged_index
+                .get_file_refs(hash)
+                .iter()
+                .filter_map(|(wad_id, item_id)| {
+                    let wad_tree = game_explorer.wad_trees().get(wad_id)?;
+                    let item = wad_tree.item_storage().get(item_id)?;
+                    Some(HashLocationDto {
+                        wad_id: *wad_id,
+                        wad_name: game_explorer
+                            .get_wad_name(wad_id)
+                            .unwrap_or(wad_tree.wad_path())
+                            .to_string(),
+                        item_id: *item_id,
+                        path: item.path().to_string(),
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    };
+
+    Ok(LookupHashResponse {
+        hash: format!("{:x}", hash),
+        wad_path: wad_path.map(|path| path.to_string()),
+        bin_names,
+        mounted_wad_items,
+        game_explorer_items,
+    })
+}
+
+/// Apply a change to the overrides and save them to the user hashtable
+fn update_wad_hashtable_overrides(
+    app: &tauri::AppHandle,
+    wad_hashtable: &WadHashtableState,
+    update: impl FnOnce(&mut WadHashtable),
+) -> eyre::Result<()> {
+    let wad_hashtables_dir = app
+        .path()
+        .app_data_dir()
+        .wrap_err("failed to get app data dir")?
+        .join(WAD_HASHTABLES_DIR);
+
+    let mut wad_hashtable = wad_hashtable.0.lock();
+    if !wad_hashtable.are_overrides_loaded() {
+        return Err(eyre!(
+            "the user wad hashtable has to be loaded before changing overrides"
+        ));
+    }
+
+    update(&mut wad_hashtable);
+    wad_hashtable
+        .save_overrides(&wad_hashtables_dir)
+        .wrap_err("failed to save user wad hashtable")
+}
+
+fn parse_path_hash(path_hash: &str) -> eyre::Result<u64> {
+    u64::from_str_radix(path_hash.trim().trim_start_matches("0x"), 16)
+        .map_err(|_| eyre!("invalid path hash: {}", path_hash))
+}
+
+/// Re-resolve the paths of everything that is already mounted against the new hashtables
+///
+/// If `path_hashes` is set, only wads which contain one of the chunks get rebuilt.
+fn rebuild_wad_trees(
+    wad_hashtable: &WadHashtableState,
+    mounted_wads: &MountedWadsState,
+    game_explorer: &GameExplorerState,
+    path_hashes: Option<&HashSet<u64>>,
+) -> eyre::Result<()> {
+    // lock in the same order as the mount commands
+    {
+        let mut mounted_wads = mounted_wads.0.lock();
+        let wad_hashtable = wad_hashtable.0.lock();
+        mounted_wads
+            .rebuild_wad_trees(&wad_hashtable, path_hashes)
+            .wrap_err("failed to rebuild mounted wad trees")?;
+    }
+
+    let wad_hashtable = wad_hashtable.0.lock();
+    let mut game_explorer = game_explorer.0.lock();
+    if game_explorer.is_initialized() {
+        game_explorer
+            .rebuild_wad_trees(&wad_hashtable, path_hashes)
+            .wrap_err("failed to rebuild game explorer wad trees")?;
+    }
+
+    Ok(())
 }
@@ -2,9 +2,125 @@ mod commands;
 
 pub use commands::*;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::state::BinHashKind;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WadHashtableStatus {
     pub is_loaded: bool,
+    /// Total amount of entries over all files, hashes which appear in several files are counted
+    /// once per file
+    pub entry_count: usize,
+    pub invalid_line_count: usize,
+    pub conflict_count: usize,
+    pub files: Vec<WadHashtableFileStatus>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WadHashtableFileStatus {
+    /// Path relative to the hashtables directory
+    pub name: String,
+    pub entry_count: usize,
+    /// Malformed lines which were skipped
+    pub invalid_line_count: usize,
+    /// Hashes which appear multiple times in the file with different paths
+    pub conflict_count: usize,
+    /// Set if the file couldn't be loaded at all
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinHashtableStatus {
+    pub is_loaded: bool,
+    pub entry_count: usize,
+    pub field_count: usize,
+    pub type_count: usize,
+    pub hash_count: usize,
+    pub files: Vec<BinHashtableFileStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinHashtableFileStatus {
+    pub kind: BinHashKind,
+    /// Path relative to the hashtables directory
+    pub name: String,
+    pub entry_count: usize,
+    /// Malformed lines and hashes wider than 32 bits which were skipped
+    pub invalid_line_count: usize,
+    /// Set if the file couldn't be loaded at all
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWadHashtablesResponse {
+    pub updated_files: Vec<String>,
+    pub unchanged_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoverWadHashesResponse {
+    pub scanned_chunk_count: usize,
+    pub unresolved_count: usize,
+    pub discovered_paths: Vec<DiscoveredWadPathDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredWadPathDto {
+    pub path_hash: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportUnresolvedHashesResponse {
+    pub chunk_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WadHashtableOverrideDto {
+    pub path_hash: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashPathResponse {
+    pub wad_path_hash: String,
+    pub bin_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LookupHashResponse {
+    pub hash: String,
+    pub wad_path: Option<String>,
+    pub bin_names: Vec<BinHashNameDto>,
+    pub mounted_wad_items: Vec<HashLocationDto>,
+    pub game_explorer_items: Vec<HashLocationDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinHashNameDto {
+    pub kind: BinHashKind,
+    pub name: String,
+}
+
+/// A chunk with the looked up hash, `wad_id` and `item_id` point at the item in its wad tree
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashLocationDto {
+    pub wad_id: Uuid,
+    pub wad_name: String,
+    pub item_id: Uuid,
+    pub path: String,
 }
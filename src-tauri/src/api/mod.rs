@@ -25,26 +25,61 @@ macro_rules! generate_command_handler {
             $crate::api::game_explorer::get_game_explorer_status,
             $crate::api::game_explorer::mount_game_explorer,
             $crate::api::game_explorer::get_game_explorer_items,
+            $crate::api::game_explorer::get_game_explorer_coverage,
             $crate::api::game_explorer::get_game_explorer_path_components,
+            $crate::api::game_explorer::get_game_explorer_item_layers,
+            $crate::api::game_explorer::get_game_explorer_mod_overlays,
+            $crate::api::game_explorer::mount_game_explorer_mod_overlay,
+            $crate::api::game_explorer::unmount_game_explorer_mod_overlay,
+            $crate::api::game_explorer::export_game_explorer_unresolved_hashes,
             // hashtable
+            $crate::api::hashtable::add_wad_hashtable_override,
+            $crate::api::hashtable::discover_wad_hashes,
+            $crate::api::hashtable::get_bin_hashtable_status,
+            $crate::api::hashtable::get_wad_hashtable_overrides,
             $crate::api::hashtable::get_wad_hashtable_status,
+            $crate::api::hashtable::hash_path,
+            $crate::api::hashtable::load_bin_hashtables,
             $crate::api::hashtable::load_wad_hashtables,
+            $crate::api::hashtable::lookup_hash,
+            $crate::api::hashtable::remove_wad_hashtable_override,
+            $crate::api::hashtable::update_wad_hashtables,
             // settings
             $crate::api::settings::get_settings,
             $crate::api::settings::update_settings,
             // wad
+            $crate::api::wad::add_mounted_wad_file,
+            $crate::api::wad::build_wad,
+            $crate::api::wad::delete_mounted_wad_items,
+            $crate::api::wad::export_wad_items_fantome,
+            $crate::api::wad::export_wad_unresolved_hashes,
+            $crate::api::wad::extract_animations_glb,
+            $crate::api::wad::extract_map_geometry_glb,
             $crate::api::wad::extract_mounted_wad,
+            $crate::api::wad::extract_skinned_mesh_glb,
             $crate::api::wad::extract_wad_items,
+            $crate::api::wad::get_bin_preview,
+            $crate::api::wad::get_chunk_bytes_range,
             $crate::api::wad::get_chunk_preview_types,
             $crate::api::wad::get_image_bytes,
             $crate::api::wad::get_mounted_wad_directory_path_components,
+            $crate::api::wad::get_mounted_wad_edits,
             $crate::api::wad::get_mounted_wads,
+            $crate::api::wad::get_skinned_mesh_glb,
+            $crate::api::wad::get_text_preview,
+            $crate::api::wad::get_wad_chunk_subchunk_bytes,
+            $crate::api::wad::get_wad_chunk_subchunks,
+            $crate::api::wad::get_wad_info,
             $crate::api::wad::get_wad_parent_items,
             $crate::api::wad::mount_wads,
             $crate::api::wad::move_mounted_wad,
+            $crate::api::wad::replace_mounted_wad_item,
+            $crate::api::wad::revert_mounted_wad_edit,
+            $crate::api::wad::save_mounted_wad,
             $crate::api::wad::search_wad,
             $crate::api::wad::unmount_wad,
             $crate::api::wad::update_mounted_wad_item_selection,
+            $crate::api::wad::verify_wad,
         ]
     };
 }
\ No newline at end of file
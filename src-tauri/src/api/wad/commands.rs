@@ -1,12 +1,40 @@
+mod build_wad;
+mod edit_mounted_wad;
+mod export_wad_items_fantome;
+mod export_wad_unresolved_hashes;
+mod extract_animations_glb;
+mod extract_map_geometry_glb;
 mod extract_wad_items;
+mod get_bin_preview;
+mod get_chunk_bytes_range;
 mod get_chunk_preview_types;
 mod get_image_bytes;
+mod get_skinned_mesh_glb;
+mod get_text_preview;
+mod get_wad_chunk_subchunks;
+mod get_wad_info;
+mod save_mounted_wad;
 mod search_wad;
-
+mod verify_wad;
+
+pub use build_wad::*;
+pub use edit_mounted_wad::*;
+pub use export_wad_items_fantome::*;
+pub use export_wad_unresolved_hashes::*;
+pub use extract_animations_glb::*;
+pub use extract_map_geometry_glb::*;
 pub use extract_wad_items::*;
+pub use get_bin_preview::*;
+pub use get_chunk_bytes_range::*;
 pub use get_chunk_preview_types::*;
 pub use get_image_bytes::*;
+pub use get_skinned_mesh_glb::*;
+pub use get_text_preview::*;
+pub use get_wad_chunk_subchunks::*;
+pub use get_wad_info::*;
+pub use save_mounted_wad::*;
 pub use search_wad::*;
+pub use verify_wad::*;
 
 use league_toolkit::wad::Wad;
 use tauri::Manager as _;
@@ -20,7 +48,7 @@ use crate::core::wad::tree::{WadTree, WadTreeItem};
 use crate::{
     api::error::ApiError,
     core::wad::tree::{WadTreeParent, WadTreePathable, WadTreeSelectable},
-    state::{MountedWadsState, SettingsState, WadHashtableState},
+    state::{ChunkCacheState, MountedWadsState, SettingsState, WadHashtableState},
     utils::actions::emit_action_progress,
 };
 use color_eyre::eyre::{self, Context, ContextCompat, eyre};
@@ -162,6 +190,7 @@ pub async fn get_mounted_wads(
                     id: *tree_id,
                     name: wad_path.file_name().unwrap().to_str().unwrap().to_string(),
                     wad_path: wad_path_string,
+                    coverage: tree.coverage().into(),
                 }
             })
             .collect_vec(),
@@ -173,6 +202,7 @@ pub async fn unmount_wad(
     app_handle: tauri::AppHandle,
     wad_id: String,
     mounted_wads: tauri::State<'_, MountedWadsState>,
+    chunk_cache: tauri::State<'_, ChunkCacheState>,
 ) -> Result<(), ApiError> {
     let mut mounted_wads_guard = mounted_wads.0.lock();
 
@@ -186,6 +216,7 @@ pub async fn unmount_wad(
     }
 
     mounted_wads_guard.unmount_wad(wad_id);
+    chunk_cache.0.lock().remove_wad(wad_id);
 
     Ok(())
 }
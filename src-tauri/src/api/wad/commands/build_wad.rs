@@ -0,0 +1,71 @@
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+use camino::Utf8Path;
+use color_eyre::eyre::Context;
+use league_toolkit::wad::WadChunkCompression;
+use uuid::Uuid;
+
+use crate::{
+    api::{error::ApiError, wad::WadChunkCompressionDto},
+    core::wad::{self, WadBuilder},
+    utils::actions::emit_action_progress,
+};
+
+#[tauri::command]
+pub async fn build_wad(
+    app_handle: tauri::AppHandle,
+    action_id: Uuid,
+    source_directory: String,
+    wad_path: String,
+    compression: WadChunkCompressionDto,
+) -> Result<(), ApiError> {
+    tracing::info!(
+        "building wad (source_directory: {}, wad_path: {})",
+        source_directory,
+        wad_path
+    );
+
+    let source_directory = PathBuf::from(source_directory);
+    if !source_directory.is_dir() {
+        return Err(ApiError::from_message(format!(
+            "source directory does not exist: {}",
+            source_directory.display()
+        )));
+    }
+
+    let compression = compression.into();
+    let mut builder = WadBuilder::new(compression);
+    if compression == WadChunkCompression::ZstdMulti {
+        builder = builder.with_subchunk_toc_path(wad::subchunk_toc_path(Utf8Path::new(&wad_path)));
+    }
+
+    let mut writer = BufWriter::new(
+        File::create(&wad_path).wrap_err(format!("failed to create wad file: {}", wad_path))?,
+    );
+
+    emit_action_progress(
+        &app_handle,
+        action_id,
+        0.0,
+        Some("Collecting files...".into()),
+    )?;
+
+    wad::build_wad_from_directory(
+        &builder,
+        &source_directory,
+        &mut writer,
+        |progress, message| {
+            emit_action_progress(
+                &app_handle,
+                action_id,
+                progress,
+                message.map(|x| x.to_string()),
+            )
+        },
+    )
+    .wrap_err(format!("failed to build wad: {}", wad_path))?;
+
+    tracing::info!("wad build complete (wad_path = {})", wad_path);
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::{ContextCompat, eyre};
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::{
+    api::{error::ApiError, wad::WadEditDto},
+    core::wad::{
+        self, WadChunkEdit,
+        tree::{WadTreeItem, WadTreePathable},
+    },
+    state::MountedWadsState,
+};
+
+#[tauri::command]
+pub async fn replace_mounted_wad_item(
+    wad_id: Uuid,
+    item_id: Uuid,
+    source_path: String,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<(), ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let source_path = PathBuf::from(source_path);
+    if !source_path.is_file() {
+        return Err(eyre!("file does not exist: {}", source_path.display()))?;
+    }
+
+    let (wad_tree, _) = mounted_wads
+        .get_wad(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let Some(WadTreeItem::File(file)) = wad_tree.item_storage().get(&item_id) else {
+        return Err(eyre!("item is not a file (item_id: {})", item_id))?;
+    };
+
+    let (path_hash, path) = (file.chunk().path_hash(), file.path());
+
+    tracing::info!(
+        "replacing mounted wad item (wad_id: {}, path: {}, source_path: {})",
+        wad_id,
+        path,
+        source_path.display()
+    );
+
+    mounted_wads
+        .edits_mut(wad_id)
+        .write_chunk(path_hash, path, source_path);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn add_mounted_wad_file(
+    wad_id: Uuid,
+    path: String,
+    source_path: String,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<(), ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let source_path = PathBuf::from(source_path);
+    if !source_path.is_file() {
+        return Err(eyre!("file does not exist: {}", source_path.display()))?;
+    }
+
+    let path = path.replace('\\', "/").trim_matches('/').to_string();
+    if path.is_empty() {
+        return Err(eyre!("invalid chunk path"))?;
+    }
+
+    if mounted_wads.get_wad(wad_id).is_none() {
+        return Err(eyre!("failed to find wad (wad_id: {})", wad_id))?;
+    }
+
+    tracing::info!(
+        "adding mounted wad file (wad_id: {}, path: {}, source_path: {})",
+        wad_id,
+        path,
+        source_path.display()
+    );
+
+    mounted_wads.edits_mut(wad_id).write_chunk(
+        wad::hash_chunk_path(&path),
+        path.into(),
+        source_path,
+    );
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_mounted_wad_items(
+    wad_id: Uuid,
+    item_ids: Vec<Uuid>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<(), ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let (wad_tree, _) = mounted_wads
+        .get_wad(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let chunks = item_ids
+        .iter()
+        .flat_map(|item_id| wad_tree.collect_files(*item_id))
+        .map(|file| (file.chunk().path_hash(), file.path()))
+        .collect_vec();
+
+    tracing::info!(
+        "deleting mounted wad items (wad_id: {}, chunk_count: {})",
+        wad_id,
+        chunks.len()
+    );
+
+    let edits = mounted_wads.edits_mut(wad_id);
+    for (path_hash, path) in chunks {
+        edits.delete_chunk(path_hash, path);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn revert_mounted_wad_edit(
+    wad_id: Uuid,
+    path_hash: String,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<(), ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let path_hash = u64::from_str_radix(&path_hash, 16)
+        .map_err(|_| ApiError::from_message("failed to parse path_hash"))?;
+
+    if mounted_wads.get_wad(wad_id).is_none() {
+        return Err(eyre!("failed to find wad (wad_id: {})", wad_id))?;
+    }
+
+    mounted_wads.edits_mut(wad_id).revert(path_hash);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_mounted_wad_edits(
+    wad_id: Uuid,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<Vec<WadEditDto>, ApiError> {
+    let mounted_wads = mounted_wads.0.lock();
+
+    let (_, wad) = mounted_wads
+        .get_wad(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let Some(edits) = mounted_wads.get_edits(wad_id) else {
+        return Ok(vec![]);
+    };
+
+    Ok(edits
+        .iter()
+        .map(|(path_hash, edit)| match edit {
+            WadChunkEdit::Write { path, source_path } => WadEditDto::Write {
+                path_hash: format!("{:x}", path_hash),
+                path: path.to_string(),
+                source_path: source_path.display().to_string(),
+                is_new: !wad.chunks().contains_key(path_hash),
+            },
+            WadChunkEdit::Delete { path } => WadEditDto::Delete {
+                path_hash: format!("{:x}", path_hash),
+                path: path.to_string(),
+            },
+        })
+        .collect_vec())
+}
@@ -0,0 +1,94 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+use color_eyre::eyre::{Context, ContextCompat};
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::api::wad::FantomeInfoDto;
+use crate::api::wad::commands::ApiError;
+use crate::core::wad::{self};
+use crate::state::SettingsState;
+use crate::utils::actions::emit_action_progress;
+use crate::{MountedWadsState, WadHashtableState};
+
+#[tauri::command]
+pub async fn export_wad_items_fantome(
+    app_handle: tauri::AppHandle,
+    wad_id: Uuid,
+    action_id: Uuid,
+    items: Vec<Uuid>,
+    fantome_path: String,
+    info: FantomeInfoDto,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    settings: tauri::State<'_, SettingsState>,
+) -> Result<(), ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let wad_name = Path::new(wad_tree.wad_path())
+        .file_name()
+        .and_then(|name| name.to_str())
+        .wrap_err("failed to get wad name")?
+        .to_string();
+
+    // get chunks for export, directories export all of their nested files
+    let chunks = items
+        .iter()
+        .flat_map(|item_id| wad_tree.collect_files(*item_id))
+        .map(|item| *item.chunk())
+        .unique_by(|chunk| chunk.path_hash())
+        .collect_vec();
+
+    let (mut decoder, _) = wad.decode();
+
+    emit_action_progress(
+        &app_handle,
+        action_id,
+        0.0,
+        Some("Creating fantome package...".into()),
+    )?;
+
+    let writer = BufWriter::new(
+        File::create(&fantome_path)
+            .wrap_err(format!("failed to create fantome file: {}", fantome_path))?,
+    );
+
+    wad::write_fantome(
+        &mut decoder,
+        &chunks,
+        &wad_name,
+        &info.into(),
+        &wad_hashtable.0.lock(),
+        writer,
+        |progress, message| {
+            emit_action_progress(
+                &app_handle,
+                action_id,
+                progress,
+                message.map(|x| x.to_string()),
+            )
+        },
+    )
+    .wrap_err(format!("failed to write fantome: {}", fantome_path))?;
+
+    tracing::info!(
+        "fantome export complete (wad_id = {}, fantome_path = {})",
+        wad_id,
+        fantome_path
+    );
+
+    if settings.0.read().open_directory_after_extraction {
+        if let Some(fantome_directory) = Path::new(&fantome_path).parent() {
+            open::that(fantome_directory).wrap_err(format!(
+                "failed to open fantome directory: {}",
+                fantome_directory.display()
+            ))?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::{fs::File, io::BufWriter};
+
+use color_eyre::eyre::{Context, ContextCompat};
+use uuid::Uuid;
+
+use crate::{
+    api::{error::ApiError, hashtable::ExportUnresolvedHashesResponse},
+    core::wad,
+    state::{MountedWadsState, WadHashtableState},
+    utils::actions::emit_action_progress,
+};
+
+/// Write the hashes of every chunk of a mounted wad whose path isn't known to `output_path`
+#[tauri::command]
+pub async fn export_wad_unresolved_hashes(
+    app_handle: tauri::AppHandle,
+    wad_id: Uuid,
+    action_id: Uuid,
+    output_path: String,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+) -> Result<ExportUnresolvedHashesResponse, ApiError> {
+    tracing::info!("exporting unresolved hashes (wad_id: {})", wad_id);
+
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let (mut decoder, _) = wad.decode();
+    let unresolved_chunks = wad::collect_unresolved_chunks(
+        wad_tree,
+        &mut decoder,
+        wad_tree.wad_path().into(),
+        &wad_hashtable.0.lock(),
+        |progress| {
+            emit_action_progress(
+                &app_handle,
+                action_id,
+                progress,
+                Some("Identifying unresolved chunks...".into()),
+            )
+        },
+    )?;
+
+    let mut writer = BufWriter::new(
+        File::create(&output_path).wrap_err(format!("failed to create file: {}", output_path))?,
+    );
+    wad::write_unresolved_chunks(&mut writer, &unresolved_chunks).wrap_err(format!(
+        "failed to write unresolved hashes: {}",
+        output_path
+    ))?;
+
+    tracing::info!(
+        "exported unresolved hashes (wad_id = {}, chunk_count = {})",
+        wad_id,
+        unresolved_chunks.len()
+    );
+
+    Ok(ExportUnresolvedHashesResponse {
+        chunk_count: unresolved_chunks.len(),
+    })
+}
@@ -0,0 +1,130 @@
+use std::{collections::HashSet, path::Path};
+
+use color_eyre::eyre::{Context, ContextCompat, eyre};
+use league_toolkit::file::LeagueFileKind;
+use uuid::Uuid;
+
+use crate::{
+    api::{error::ApiError, wad::ExtractAnimationsResponse},
+    core::{
+        gltf::{self, AnimationClipSource},
+        wad::tree::{WadTreeItem, WadTreePathable},
+    },
+    state::MountedWadsState,
+    utils::actions::emit_action_progress,
+};
+
+/// Convert animations to clips of a single glb, bound to the joints of a skeleton
+///
+/// Directories in `animation_item_ids` contribute every animation nested inside of them, clips are
+/// named after the file of their animation.
+#[tauri::command]
+pub async fn extract_animations_glb(
+    app_handle: tauri::AppHandle,
+    wad_id: Uuid,
+    action_id: Uuid,
+    skeleton_item_id: Uuid,
+    animation_item_ids: Vec<Uuid>,
+    output_path: String,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<ExtractAnimationsResponse, ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let skeleton_chunk = match wad_tree.item_storage().get(&skeleton_item_id) {
+        Some(WadTreeItem::File(file)) => *file.chunk(),
+        Some(_) => Err(eyre!("item is not a file (item_id: {})", skeleton_item_id))?,
+        None => Err(eyre!("failed to find item (item_id: {})", skeleton_item_id))?,
+    };
+
+    let mut animation_files = Vec::new();
+    let mut visited = HashSet::new();
+    for item_id in animation_item_ids {
+        for file in wad_tree.collect_files(item_id) {
+            if visited.insert(file.id()) {
+                animation_files.push(file);
+            }
+        }
+    }
+
+    emit_action_progress(
+        &app_handle,
+        action_id,
+        0.0,
+        Some("Loading animations...".into()),
+    )?;
+
+    let (mut decoder, _) = wad.decode();
+    let skeleton_data = decoder
+        .load_chunk_decompressed(&skeleton_chunk)
+        .wrap_err(format!(
+            "failed to load chunk (path_hash: {:x})",
+            skeleton_chunk.path_hash()
+        ))?;
+
+    let mut animations = Vec::new();
+    let mut clip_names = HashSet::new();
+    for file in animation_files {
+        let data = decoder
+            .load_chunk_decompressed(file.chunk())
+            .wrap_err(format!(
+                "failed to load chunk (path_hash: {:x})",
+                file.chunk().path_hash()
+            ))?;
+        if LeagueFileKind::identify_from_bytes(&data) != LeagueFileKind::Animation {
+            continue;
+        }
+
+        // animations of different folders can share a file name
+        let path = file.path();
+        let stem = Path::new(path.as_ref())
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(path.as_ref());
+        let name = match clip_names.contains(stem) {
+            true => path.to_string(),
+            false => stem.to_string(),
+        };
+        clip_names.insert(name.clone());
+
+        animations.push((name, data));
+    }
+
+    if animations.is_empty() {
+        return Err(ApiError::from_message("No animations were selected"));
+    }
+
+    let animations = animations
+        .iter()
+        .map(|(name, data)| AnimationClipSource {
+            name: name.clone(),
+            data,
+        })
+        .collect::<Vec<_>>();
+
+    let progress_offset = 0.1;
+    let (glb, clip_names) =
+        gltf::animations_to_glb(&skeleton_data, &animations, |progress, message| {
+            emit_action_progress(
+                &app_handle,
+                action_id,
+                progress_offset + (progress * (1.0 - progress_offset)),
+                message.map(|x| x.to_string()),
+            )
+        })?;
+
+    std::fs::write(&output_path, glb).wrap_err(format!("failed to write glb: {}", output_path))?;
+
+    tracing::info!(
+        "extracted animations (clip_count = {}, output_path = {})",
+        clip_names.len(),
+        output_path
+    );
+
+    Ok(ExtractAnimationsResponse {
+        skipped_count: animations.len() - clip_names.len(),
+        clip_names,
+    })
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{self, Context, ContextCompat, eyre};
+use image::ImageFormat;
+use league_toolkit::{
+    file::LeagueFileKind,
+    meta::BinTree,
+    render::texture::Texture,
+    wad::{WadChunk, WadDecoder},
+};
+use uuid::Uuid;
+
+use crate::{
+    api::{error::ApiError, wad::ExtractMapGeometryResponse},
+    core::{
+        gltf,
+        mapgeo::{self, MapGeometry},
+        wad::{
+            self,
+            tree::{WadTreeItem, WadTreePathable},
+        },
+    },
+    state::MountedWadsState,
+    utils::actions::emit_action_progress,
+};
+
+/// Convert map geometry to glb
+///
+/// With `resolve_textures` the diffuse textures of the materials are looked up in the materials
+/// bin next to the map geometry and embedded from the same wad. With `max_meshes_per_file` the
+/// meshes are split across numbered files next to `output_path`.
+#[tauri::command]
+pub async fn extract_map_geometry_glb(
+    app_handle: tauri::AppHandle,
+    wad_id: Uuid,
+    action_id: Uuid,
+    item_id: Uuid,
+    output_path: String,
+    resolve_textures: bool,
+    max_meshes_per_file: Option<usize>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<ExtractMapGeometryResponse, ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let (chunk, path) = match wad_tree.item_storage().get(&item_id) {
+        Some(WadTreeItem::File(file)) => (*file.chunk(), file.path()),
+        Some(_) => Err(eyre!("item is not a file (item_id: {})", item_id))?,
+        None => Err(eyre!("failed to find item (item_id: {})", item_id))?,
+    };
+
+    emit_action_progress(
+        &app_handle,
+        action_id,
+        0.0,
+        Some("Loading map geometry...".into()),
+    )?;
+
+    let (mut decoder, chunks) = wad.decode();
+    let data = load_chunk(&mut decoder, &chunk)?;
+    if LeagueFileKind::identify_from_bytes(&data) != LeagueFileKind::MapGeometry {
+        return Err(eyre!("item is not map geometry (item_id: {})", item_id))?;
+    }
+    let geometry =
+        MapGeometry::read(&mut Cursor::new(data)).wrap_err("failed to parse map geometry")?;
+
+    let materials = match resolve_textures {
+        true => load_map_materials(&mut decoder, chunks, &path),
+        false => None,
+    };
+
+    let progress_offset = 0.1;
+    let gltf::MapGeometryGlbs { glbs, mesh_count } = gltf::map_geometry_to_glbs(
+        &geometry,
+        max_meshes_per_file,
+        |material| {
+            let texture_path = mapgeo::find_diffuse_texture(materials.as_ref()?, material)?;
+            match load_texture_png(&mut decoder, chunks, texture_path) {
+                Ok(texture) => texture,
+                Err(error) => {
+                    tracing::warn!("failed to load texture: {} ({})", texture_path, error);
+                    None
+                }
+            }
+        },
+        |progress, message| {
+            emit_action_progress(
+                &app_handle,
+                action_id,
+                progress_offset + (progress * (1.0 - progress_offset)),
+                message.map(|x| x.to_string()),
+            )
+        },
+    )?;
+
+    let output_path = PathBuf::from(output_path);
+    let output_paths = match glbs.len() {
+        1 => vec![output_path],
+        _ => (1..=glbs.len())
+            .map(|i| numbered_output_path(&output_path, i))
+            .collect(),
+    };
+
+    for (glb, output_path) in glbs.iter().zip(&output_paths) {
+        std::fs::write(output_path, glb)
+            .wrap_err(format!("failed to write glb: {}", output_path.display()))?;
+    }
+
+    tracing::info!(
+        "extracted map geometry (item_id = {}, mesh_count = {}, file_count = {})",
+        item_id,
+        mesh_count,
+        output_paths.len()
+    );
+
+    Ok(ExtractMapGeometryResponse {
+        mesh_count,
+        output_paths: output_paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+    })
+}
+
+fn load_chunk(decoder: &mut WadDecoder<File>, chunk: &WadChunk) -> eyre::Result<Box<[u8]>> {
+    decoder.load_chunk_decompressed(chunk).wrap_err(format!(
+        "failed to load chunk (path_hash: {:x})",
+        chunk.path_hash()
+    ))
+}
+
+/// Maps without a materials bin still get converted, just without textures
+fn load_map_materials(
+    decoder: &mut WadDecoder<File>,
+    chunks: &HashMap<u64, WadChunk>,
+    map_geometry_path: &str,
+) -> Option<BinTree> {
+    let materials_path = mapgeo::map_materials_path(map_geometry_path);
+    let Some(chunk) = chunks.get(&wad::hash_chunk_path(&materials_path)) else {
+        tracing::warn!("failed to find map materials: {}", materials_path);
+        return None;
+    };
+
+    let materials = load_chunk(decoder, chunk).and_then(|data| {
+        BinTree::from_reader(&mut Cursor::new(data)).wrap_err("failed to parse map materials")
+    });
+
+    match materials {
+        Ok(materials) => Some(materials),
+        Err(error) => {
+            tracing::warn!(
+                "failed to load map materials: {} ({})",
+                materials_path,
+                error
+            );
+            None
+        }
+    }
+}
+
+/// Decode a texture of the wad to PNG, bins may still refer to textures by their `.dds` path after
+/// they were converted to `.tex`
+fn load_texture_png(
+    decoder: &mut WadDecoder<File>,
+    chunks: &HashMap<u64, WadChunk>,
+    texture_path: &str,
+) -> eyre::Result<Option<Vec<u8>>> {
+    let tex_path = Path::new(texture_path).with_extension("tex");
+    let chunk = [Some(texture_path), tex_path.to_str()]
+        .into_iter()
+        .flatten()
+        .find_map(|path| chunks.get(&wad::hash_chunk_path(path)));
+    let Some(chunk) = chunk else {
+        return Ok(None);
+    };
+
+    let data = load_chunk(decoder, chunk)?;
+    let texture =
+        Texture::from_reader(&mut Cursor::new(data)).wrap_err("failed to parse texture")?;
+    let image = texture
+        .decode_mipmap(0)
+        .wrap_err("failed to decode texture")?
+        .into_rgba_image()
+        .wrap_err("failed to convert texture to image")?;
+
+    let mut writer = Cursor::new(Vec::new());
+    image
+        .write_to(&mut writer, ImageFormat::Png)
+        .wrap_err("failed to encode image")?;
+
+    Ok(Some(writer.into_inner()))
+}
+
+/// `map.glb` becomes `map_1.glb`, `map_2.glb`, ...
+fn numbered_output_path(output_path: &Path, index: usize) -> PathBuf {
+    let stem = output_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    output_path.with_file_name(format!("{}_{}.glb", stem, index))
+}
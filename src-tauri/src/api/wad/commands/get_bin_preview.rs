@@ -0,0 +1,57 @@
+use color_eyre::eyre::{Context, ContextCompat, eyre};
+use league_toolkit::file::LeagueFileKind;
+use uuid::Uuid;
+
+use crate::{
+    api::error::ApiError,
+    core::{
+        bin::{self, BinPreview},
+        wad::tree::WadTreeItem,
+    },
+    state::{
+        BinHashtableState, ChunkCacheKey, ChunkCacheState, MountedWadsState, WadHashtableState,
+    },
+};
+
+/// Parse a property BIN chunk into a tree of entries, fields and values, names which aren't in
+/// the bin hashtables are returned as hex
+#[tauri::command]
+pub async fn get_bin_preview(
+    wad_id: Uuid,
+    item_id: Uuid,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+    bin_hashtable: tauri::State<'_, BinHashtableState>,
+    chunk_cache: tauri::State<'_, ChunkCacheState>,
+) -> Result<BinPreview, ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let chunk = match wad_tree.item_storage().get(&item_id) {
+        Some(WadTreeItem::File(file)) => *file.chunk(),
+        Some(_) => Err(eyre!("item is not a file (item_id: {})", item_id))?,
+        None => Err(eyre!("failed to find item (item_id: {})", item_id))?,
+    };
+
+    let cache_key = ChunkCacheKey::new(wad_id, &chunk);
+    let chunk_data = chunk_cache.0.lock().get_or_try_insert_with(cache_key, || {
+        let (mut decoder, _) = wad.decode();
+        decoder.load_chunk_decompressed(&chunk).wrap_err(format!(
+            "failed to load chunk (path_hash: {:x})",
+            chunk.path_hash()
+        ))
+    })?;
+
+    match LeagueFileKind::identify_from_bytes(&chunk_data) {
+        LeagueFileKind::PropertyBin | LeagueFileKind::PropertyBinOverride => {}
+        _ => Err(eyre!("item is not a bin file (item_id: {})", item_id))?,
+    }
+
+    Ok(bin::read_bin_preview(
+        &chunk_data,
+        &bin_hashtable.0.lock(),
+        &wad_hashtable.0.lock(),
+    )?)
+}
@@ -0,0 +1,50 @@
+use color_eyre::eyre::{Context, ContextCompat, eyre};
+use uuid::Uuid;
+
+use crate::{
+    api::{error::ApiError, wad::ChunkBytesRangeResponse},
+    core::wad::tree::WadTreeItem,
+    state::{ChunkCacheKey, ChunkCacheState, MountedWadsState},
+};
+
+/// Read up to `length` bytes of the decompressed data of a chunk, starting at `offset`
+///
+/// The decompressed chunk is cached so paging through it only decompresses it once.
+#[tauri::command]
+pub async fn get_chunk_bytes_range(
+    wad_id: Uuid,
+    item_id: Uuid,
+    offset: usize,
+    length: usize,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    chunk_cache: tauri::State<'_, ChunkCacheState>,
+) -> Result<ChunkBytesRangeResponse, ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let chunk = match wad_tree.item_storage().get(&item_id) {
+        Some(WadTreeItem::File(file)) => *file.chunk(),
+        Some(_) => Err(eyre!("item is not a file (item_id: {})", item_id))?,
+        None => Err(eyre!("failed to find item (item_id: {})", item_id))?,
+    };
+
+    let cache_key = ChunkCacheKey::new(wad_id, &chunk);
+    let chunk_data = chunk_cache.0.lock().get_or_try_insert_with(cache_key, || {
+        let (mut decoder, _) = wad.decode();
+        decoder.load_chunk_decompressed(&chunk).wrap_err(format!(
+            "failed to load chunk (path_hash: {:x})",
+            chunk.path_hash()
+        ))
+    })?;
+
+    let start = offset.min(chunk_data.len());
+    let end = start.saturating_add(length).min(chunk_data.len());
+
+    Ok(ChunkBytesRangeResponse {
+        offset: start,
+        bytes: chunk_data[start..end].to_vec(),
+        total_size: chunk_data.len(),
+    })
+}
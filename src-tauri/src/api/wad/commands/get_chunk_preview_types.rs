@@ -1,32 +1,62 @@
+use color_eyre::eyre::{self, Context, ContextCompat, eyre};
+use league_toolkit::wad::Wad;
+use std::fs::File;
 use uuid::Uuid;
 
-use crate::{api::error::ApiError, core::wad::WadChunkPreviewType, state::MountedWadsState};
+use crate::{
+    api::error::ApiError,
+    core::wad::{
+        WadChunkPreviewRegistry, WadChunkPreviewType,
+        tree::{WadTree, WadTreeItem, WadTreePathable},
+    },
+    state::{ChunkCache, ChunkCacheKey, ChunkCacheState, MountedWadsState},
+};
 
 #[tauri::command]
-pub fn get_chunk_preview_types(
+pub async fn get_chunk_preview_types(
     wad_id: Uuid,
     item_id: Uuid,
     mounted_wads: tauri::State<'_, MountedWadsState>,
+    chunk_cache: tauri::State<'_, ChunkCacheState>,
 ) -> Result<Vec<WadChunkPreviewType>, ApiError> {
-    let mounted_wads_guard = mounted_wads.0.lock();
+    let mut mounted_wads = mounted_wads.0.lock();
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
 
-    let Some(wad_tree) = mounted_wads_guard.wad_trees().get(&wad_id) else {
-        return Err(ApiError::from_message(format!(
-            "Wad tree not found: {}",
-            wad_id
-        )));
-    };
+    Ok(load_chunk_preview_types(
+        wad_id,
+        wad_tree,
+        wad,
+        item_id,
+        &mut chunk_cache.0.lock(),
+    )?)
+}
 
-    let Some(wad) = mounted_wads_guard.wads().get(&wad_id) else {
-        return Err(ApiError::from_message(format!("Wad not found: {}", wad_id)));
+/// Identify a chunk from its decompressed data and get the preview types which support it
+///
+/// The decompressed chunk goes through the chunk cache since it's usually previewed right after.
+fn load_chunk_preview_types(
+    wad_id: Uuid,
+    wad_tree: &WadTree,
+    wad: &mut Wad<File>,
+    item_id: Uuid,
+    chunk_cache: &mut ChunkCache,
+) -> eyre::Result<Vec<WadChunkPreviewType>> {
+    let (chunk, path) = match wad_tree.item_storage().get(&item_id) {
+        Some(WadTreeItem::File(file)) => (*file.chunk(), file.path()),
+        Some(_) => Err(eyre!("item is not a file (item_id: {})", item_id))?,
+        None => Err(eyre!("failed to find item (item_id: {})", item_id))?,
     };
 
-    let Some(item) = wad_tree.item_storage().get(&item_id) else {
-        return Err(ApiError::from_message(format!(
-            "Item not found: {}",
-            item_id
-        )));
-    };
+    let cache_key = ChunkCacheKey::new(wad_id, &chunk);
+    let chunk_data = chunk_cache.get_or_try_insert_with(cache_key, || {
+        let (mut decoder, _) = wad.decode();
+        decoder.load_chunk_decompressed(&chunk).wrap_err(format!(
+            "failed to load chunk (path_hash: {:x})",
+            chunk.path_hash()
+        ))
+    })?;
 
-    Ok(vec![WadChunkPreviewType::Image])
+    Ok(WadChunkPreviewRegistry::default().preview_types(&path, &chunk_data))
 }
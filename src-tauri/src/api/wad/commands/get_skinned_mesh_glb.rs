@@ -0,0 +1,131 @@
+use std::{fs::File, path::Path};
+
+use color_eyre::eyre::{self, Context, ContextCompat, eyre};
+use league_toolkit::{
+    file::LeagueFileKind,
+    wad::{Wad, WadChunk},
+};
+use uuid::Uuid;
+
+use crate::{
+    api::error::ApiError,
+    core::{
+        gltf,
+        wad::{
+            self,
+            tree::{WadTree, WadTreeItem, WadTreePathable},
+        },
+    },
+    state::{ChunkCache, ChunkCacheKey, ChunkCacheState, MountedWadsState},
+};
+
+/// Convert a skinned mesh to glb for previewing it
+///
+/// Without `skeleton_item_id` the skeleton next to the mesh, with the same name, is used if
+/// there is one.
+#[tauri::command]
+pub async fn get_skinned_mesh_glb(
+    wad_id: Uuid,
+    item_id: Uuid,
+    skeleton_item_id: Option<Uuid>,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    chunk_cache: tauri::State<'_, ChunkCacheState>,
+) -> Result<Vec<u8>, ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    Ok(convert_skinned_mesh(
+        wad_tree,
+        wad,
+        &mut chunk_cache.0.lock(),
+        wad_id,
+        item_id,
+        skeleton_item_id,
+    )?)
+}
+
+/// Convert a skinned mesh to glb and write it to `output_path`
+#[tauri::command]
+pub async fn extract_skinned_mesh_glb(
+    wad_id: Uuid,
+    item_id: Uuid,
+    skeleton_item_id: Option<Uuid>,
+    output_path: String,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    chunk_cache: tauri::State<'_, ChunkCacheState>,
+) -> Result<(), ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let glb = convert_skinned_mesh(
+        wad_tree,
+        wad,
+        &mut chunk_cache.0.lock(),
+        wad_id,
+        item_id,
+        skeleton_item_id,
+    )?;
+    std::fs::write(&output_path, glb).wrap_err(format!("failed to write glb: {}", output_path))?;
+
+    tracing::info!(
+        "extracted skinned mesh (item_id = {}, output_path = {})",
+        item_id,
+        output_path
+    );
+
+    Ok(())
+}
+
+fn convert_skinned_mesh(
+    wad_tree: &WadTree,
+    wad: &mut Wad<File>,
+    chunk_cache: &mut ChunkCache,
+    wad_id: Uuid,
+    item_id: Uuid,
+    skeleton_item_id: Option<Uuid>,
+) -> eyre::Result<Vec<u8>> {
+    let (mesh_chunk, mesh_path) = get_item_chunk(wad_tree, item_id)?;
+    let skeleton_chunk = match skeleton_item_id {
+        Some(skeleton_item_id) => Some(get_item_chunk(wad_tree, skeleton_item_id)?.0),
+        None => find_sibling_skeleton(wad, &mesh_path),
+    };
+
+    let mut load_chunk = |chunk: WadChunk| {
+        chunk_cache.get_or_try_insert_with(ChunkCacheKey::new(wad_id, &chunk), || {
+            let (mut decoder, _) = wad.decode();
+            decoder.load_chunk_decompressed(&chunk).wrap_err(format!(
+                "failed to load chunk (path_hash: {:x})",
+                chunk.path_hash()
+            ))
+        })
+    };
+
+    let mesh_data = load_chunk(mesh_chunk)?;
+    if LeagueFileKind::identify_from_bytes(&mesh_data) != LeagueFileKind::SimpleSkin {
+        return Err(eyre!("item is not a skinned mesh (item_id: {})", item_id));
+    }
+
+    let skeleton_data = skeleton_chunk.map(&mut load_chunk).transpose()?;
+
+    gltf::skinned_mesh_to_glb(&mesh_data, skeleton_data.as_deref())
+}
+
+fn get_item_chunk(wad_tree: &WadTree, item_id: Uuid) -> eyre::Result<(WadChunk, String)> {
+    match wad_tree.item_storage().get(&item_id) {
+        Some(WadTreeItem::File(file)) => Ok((*file.chunk(), file.path().to_string())),
+        Some(_) => Err(eyre!("item is not a file (item_id: {})", item_id)),
+        None => Err(eyre!("failed to find item (item_id: {})", item_id)),
+    }
+}
+
+/// Skeletons usually sit next to their mesh, e.g. `aatrox.skn` and `aatrox.skl`
+fn find_sibling_skeleton(wad: &Wad<File>, mesh_path: &str) -> Option<WadChunk> {
+    let skeleton_path = Path::new(mesh_path).with_extension("skl");
+    let path_hash = wad::hash_chunk_path(skeleton_path.to_str()?);
+
+    wad.chunks().get(&path_hash).copied()
+}
@@ -0,0 +1,51 @@
+use color_eyre::eyre::{Context, ContextCompat, eyre};
+use uuid::Uuid;
+
+use crate::{
+    api::error::ApiError,
+    core::{
+        text::{self, TEXT_PREVIEW_MAX_SIZE, TextPreview},
+        wad::tree::{WadTreeItem, WadTreePathable},
+    },
+    state::{ChunkCacheKey, ChunkCacheState, MountedWadsState},
+};
+
+/// Decode a text chunk, e.g. a lua script or a json config, along with a language hint for
+/// highlighting. Content past [`TEXT_PREVIEW_MAX_SIZE`] is cut off.
+#[tauri::command]
+pub async fn get_text_preview(
+    wad_id: Uuid,
+    item_id: Uuid,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    chunk_cache: tauri::State<'_, ChunkCacheState>,
+) -> Result<TextPreview, ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    let (chunk, path) = match wad_tree.item_storage().get(&item_id) {
+        Some(WadTreeItem::File(file)) => (*file.chunk(), file.path()),
+        Some(_) => Err(eyre!("item is not a file (item_id: {})", item_id))?,
+        None => Err(eyre!("failed to find item (item_id: {})", item_id))?,
+    };
+
+    let cache_key = ChunkCacheKey::new(wad_id, &chunk);
+    let chunk_data = chunk_cache.0.lock().get_or_try_insert_with(cache_key, || {
+        let (mut decoder, _) = wad.decode();
+        decoder.load_chunk_decompressed(&chunk).wrap_err(format!(
+            "failed to load chunk (path_hash: {:x})",
+            chunk.path_hash()
+        ))
+    })?;
+
+    if !text::is_text_chunk(path.as_ref(), &chunk_data) {
+        Err(eyre!("item is not a text file (item_id: {})", item_id))?;
+    }
+
+    Ok(text::read_text_preview(
+        path.as_ref(),
+        &chunk_data,
+        TEXT_PREVIEW_MAX_SIZE,
+    ))
+}
@@ -0,0 +1,93 @@
+use std::fs::File;
+
+use color_eyre::eyre::{self, Context, ContextCompat, eyre};
+use itertools::Itertools;
+use league_toolkit::wad::WadChunk;
+use uuid::Uuid;
+
+use crate::{
+    api::{error::ApiError, wad::WadSubchunkDto},
+    core::wad::{self, WadSubchunk, tree::WadTreeItem},
+    state::{MountedWads, MountedWadsState},
+};
+
+#[tauri::command]
+pub async fn get_wad_chunk_subchunks(
+    wad_id: Uuid,
+    item_id: Uuid,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<Vec<WadSubchunkDto>, ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let chunk = get_item_chunk(&mounted_wads, wad_id, item_id)?;
+    let subchunks = get_chunk_subchunks(&mut mounted_wads, wad_id, &chunk)?;
+
+    Ok(subchunks.iter().map(WadSubchunkDto::from).collect_vec())
+}
+
+/// Decompress a single subchunk of a chunk, `subchunk_index` is the index of the subchunk in the
+/// `.subchunktoc` of the wad
+#[tauri::command]
+pub async fn get_wad_chunk_subchunk_bytes(
+    wad_id: Uuid,
+    item_id: Uuid,
+    subchunk_index: usize,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<Vec<u8>, ApiError> {
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let chunk = get_item_chunk(&mounted_wads, wad_id, item_id)?;
+    let subchunks = get_chunk_subchunks(&mut mounted_wads, wad_id, &chunk)?;
+    let subchunk = subchunks
+        .iter()
+        .find(|subchunk| subchunk.index == subchunk_index)
+        .wrap_err(format!(
+            "failed to find subchunk (index: {})",
+            subchunk_index
+        ))?;
+
+    let (wad_tree, _) = mounted_wads
+        .get_wad(wad_id)
+        .wrap_err("failed to find wad")?;
+    let mut wad_file = File::open(wad_tree.wad_path())
+        .wrap_err(format!("failed to open wad: {}", wad_tree.wad_path()))?;
+
+    Ok(wad::read_subchunk(&mut wad_file, &chunk, subchunk)
+        .wrap_err(format!(
+            "failed to read subchunk (path_hash: {:x}, index: {})",
+            chunk.path_hash(),
+            subchunk_index
+        ))?
+        .into_vec())
+}
+
+fn get_item_chunk(
+    mounted_wads: &MountedWads,
+    wad_id: Uuid,
+    item_id: Uuid,
+) -> eyre::Result<WadChunk> {
+    let (wad_tree, _) = mounted_wads
+        .get_wad(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    match wad_tree.item_storage().get(&item_id) {
+        Some(WadTreeItem::File(file)) => Ok(*file.chunk()),
+        Some(_) => Err(eyre!("item is not a file (item_id: {})", item_id)),
+        None => Err(eyre!("failed to find item (item_id: {})", item_id)),
+    }
+}
+
+fn get_chunk_subchunks(
+    mounted_wads: &mut MountedWads,
+    wad_id: Uuid,
+    chunk: &WadChunk,
+) -> eyre::Result<Vec<WadSubchunk>> {
+    let subchunks = mounted_wads
+        .get_subchunks(wad_id)?
+        .wrap_err(format!("wad has no subchunk toc (wad_id: {})", wad_id))?;
+
+    Ok(subchunks
+        .get(chunk.path_hash())
+        .map(<[WadSubchunk]>::to_vec)
+        .unwrap_or_default())
+}
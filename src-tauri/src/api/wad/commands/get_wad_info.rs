@@ -0,0 +1,78 @@
+use std::{collections::HashMap, fs::File};
+
+use color_eyre::eyre::{Context, ContextCompat};
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        error::ApiError,
+        wad::{WadChunkCompressionDto, WadInfoDto},
+    },
+    core::wad::WadToc,
+    state::MountedWadsState,
+};
+
+#[tauri::command]
+pub async fn get_wad_info(
+    wad_id: Uuid,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<WadInfoDto, ApiError> {
+    let wad_path = {
+        let mounted_wads = mounted_wads.0.lock();
+        let (wad_tree, _) = mounted_wads
+            .get_wad(wad_id)
+            .wrap_err("failed to find wad")?;
+
+        wad_tree.wad_path().to_string()
+    };
+
+    let mut file = File::open(&wad_path).wrap_err(format!("failed to open wad: {}", wad_path))?;
+    let file_size = file.metadata()?.len();
+    let toc = WadToc::read(&mut file).wrap_err(format!("failed to read wad toc: {}", wad_path))?;
+
+    let mut compression_histogram = HashMap::<WadChunkCompressionDto, usize>::new();
+    let mut unknown_compression_count = 0;
+    for entry in &toc.entries {
+        match entry.compression_type() {
+            Some(compression) => *compression_histogram.entry(compression.into()).or_default() += 1,
+            None => unknown_compression_count += 1,
+        }
+    }
+
+    Ok(WadInfoDto {
+        major: toc.major,
+        minor: toc.minor,
+        signature: toc
+            .signature
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .join(""),
+        checksum: format!("{:x}", toc.checksum),
+        toc_offset: toc.toc_offset,
+        file_size,
+        chunk_count: toc.entries.len(),
+        compressed_size: toc
+            .entries
+            .iter()
+            .map(|entry| entry.compressed_size as u64)
+            .sum(),
+        uncompressed_size: toc
+            .entries
+            .iter()
+            .map(|entry| entry.uncompressed_size as u64)
+            .sum(),
+        compression_histogram,
+        unknown_compression_count,
+        duplicated_count: toc
+            .entries
+            .iter()
+            .filter(|entry| entry.is_duplicated)
+            .count(),
+        subchunked_count: toc
+            .entries
+            .iter()
+            .filter(|entry| entry.subchunk_count > 0)
+            .count(),
+    })
+}
@@ -0,0 +1,115 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use camino::Utf8Path;
+use color_eyre::eyre::{Context, ContextCompat};
+use league_toolkit::wad::WadChunkCompression;
+use uuid::Uuid;
+
+use crate::{
+    api::{error::ApiError, wad::WadChunkCompressionDto},
+    core::wad::{self, WadBuilder, WadSubchunks, WadToc},
+    state::MountedWadsState,
+    utils::actions::emit_action_progress,
+};
+
+#[tauri::command]
+pub async fn save_mounted_wad(
+    app_handle: tauri::AppHandle,
+    wad_id: Uuid,
+    action_id: Uuid,
+    wad_path: String,
+    compression: WadChunkCompressionDto,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+) -> Result<(), ApiError> {
+    tracing::info!(
+        "saving mounted wad (wad_id: {}, wad_path: {})",
+        wad_id,
+        wad_path
+    );
+
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let (wad_tree, _) = mounted_wads
+        .get_wad(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    // mounted wads are read while saving, so none of them can be the destination
+    if mounted_wads
+        .wad_trees()
+        .values()
+        .any(|wad_tree| is_same_file(wad_tree.wad_path(), &wad_path))
+    {
+        return Err(ApiError::from_message(
+            "Cannot overwrite a mounted wad, save it to a different path",
+        ));
+    }
+
+    let source_wad_path = wad_tree.wad_path().to_string();
+    let toc = WadToc::read(&mut File::open(&source_wad_path)?)
+        .wrap_err(format!("failed to read wad toc: {}", source_wad_path))?;
+
+    let (wad, edits) = mounted_wads
+        .get_wad_with_edits_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+    let (mut decoder, chunks) = wad.decode();
+
+    let subchunks = WadSubchunks::load(&mut decoder, chunks, &toc, &source_wad_path)?;
+
+    let compression = compression.into();
+    let mut builder = WadBuilder::new(compression);
+    if compression == WadChunkCompression::ZstdMulti || subchunks.is_some() {
+        builder = builder.with_subchunk_toc_path(wad::subchunk_toc_path(Utf8Path::new(&wad_path)));
+    }
+
+    // write next to the destination and move it into place once the wad is complete, so a failed
+    // save doesn't leave a truncated wad behind
+    let temp_path = format!("{}.tmp", wad_path);
+    let mut writer = BufWriter::new(
+        File::create(&temp_path).wrap_err(format!("failed to create wad file: {}", temp_path))?,
+    );
+
+    let result = wad::write_edited_wad(
+        &builder,
+        &mut decoder,
+        chunks,
+        subchunks.as_ref(),
+        edits,
+        &mut writer,
+        |progress, message| {
+            emit_action_progress(
+                &app_handle,
+                action_id,
+                progress,
+                message.map(|x| x.to_string()),
+            )
+        },
+    )
+    .and_then(|_| Ok(writer.flush()?));
+    drop(writer);
+
+    if let Err(error) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error.wrap_err(format!("failed to save wad: {}", wad_path)))?;
+    }
+
+    fs::rename(&temp_path, &wad_path)
+        .wrap_err(format!("failed to move wad into place: {}", wad_path))?;
+
+    mounted_wads.clear_edits(wad_id);
+
+    tracing::info!("wad saved (wad_id = {}, wad_path = {})", wad_id, wad_path);
+
+    Ok(())
+}
+
+/// Paths which don't exist yet can't refer to an existing file
+fn is_same_file(a: impl AsRef<Path>, b: impl AsRef<Path>) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
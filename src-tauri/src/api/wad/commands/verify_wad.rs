@@ -0,0 +1,67 @@
+use std::fs::File;
+
+use color_eyre::eyre::{Context, ContextCompat};
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::{
+    api::{
+        error::ApiError,
+        wad::{VerifyWadResponse, WadChunkVerificationFailureDto},
+    },
+    core::wad::{self, WadChecksumKind, WadToc},
+    state::{MountedWadsState, WadHashtableState},
+    utils::actions::emit_action_progress,
+};
+
+#[tauri::command]
+pub async fn verify_wad(
+    app_handle: tauri::AppHandle,
+    wad_id: Uuid,
+    action_id: Uuid,
+    mounted_wads: tauri::State<'_, MountedWadsState>,
+    wad_hashtable: tauri::State<'_, WadHashtableState>,
+) -> Result<VerifyWadResponse, ApiError> {
+    tracing::info!("verifying wad (wad_id: {})", wad_id);
+
+    let mut mounted_wads = mounted_wads.0.lock();
+
+    let (wad_tree, wad) = mounted_wads
+        .get_wad_mut(wad_id)
+        .wrap_err("failed to find wad")?;
+
+    // the checksums aren't exposed by league_toolkit so the TOC has to be read again
+    let toc = WadToc::read(&mut File::open(wad_tree.wad_path())?)
+        .wrap_err(format!("failed to read wad toc: {}", wad_tree.wad_path()))?;
+
+    let (mut decoder, chunks) = wad.decode();
+    let failures = wad::verify_wad_chunks(
+        &mut decoder,
+        chunks,
+        &toc,
+        &wad_hashtable.0.lock(),
+        |progress, message| {
+            emit_action_progress(
+                &app_handle,
+                action_id,
+                progress,
+                message.map(|x| x.to_string()),
+            )
+        },
+    )?;
+
+    tracing::info!(
+        "wad verification complete (wad_id = {}, failure_count = {})",
+        wad_id,
+        failures.len()
+    );
+
+    Ok(VerifyWadResponse {
+        chunk_count: toc.entries.len(),
+        is_checksum_verified: toc.checksum_kind() == WadChecksumKind::Xxh3,
+        failures: failures
+            .iter()
+            .map(WadChunkVerificationFailureDto::from)
+            .collect_vec(),
+    })
+}
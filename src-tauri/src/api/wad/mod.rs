@@ -3,9 +3,12 @@ mod commands;
 pub use commands::*;
 use league_toolkit::{file::LeagueFileKind, wad::WadChunkCompression};
 
-use crate::core::wad::tree::{WadTreeDirectory, WadTreeFile, WadTreeItem, WadTreePathable};
+use crate::core::wad::{
+    FantomeInfo, WadChunkVerificationError, WadChunkVerificationFailure, WadSubchunk,
+    tree::{WadTreeCoverage, WadTreeDirectory, WadTreeFile, WadTreeItem, WadTreePathable},
+};
 use serde::{self, Deserialize, Serialize};
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 use uuid::Uuid;
 
 #[derive(Serialize, Deserialize)]
@@ -42,6 +45,28 @@ pub struct MountedWadDto {
     pub id: Uuid,
     pub name: String,
     pub wad_path: String,
+    pub coverage: HashCoverageDto,
+}
+
+/// How many chunks have a path in the hashtable, the rest are shown as hex hashes
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashCoverageDto {
+    pub chunk_count: usize,
+    pub resolved_count: usize,
+    pub unresolved_count: usize,
+    pub percentage: f64,
+}
+
+impl From<WadTreeCoverage> for HashCoverageDto {
+    fn from(value: WadTreeCoverage) -> Self {
+        Self {
+            chunk_count: value.chunk_count,
+            resolved_count: value.resolved_count,
+            unresolved_count: value.unresolved_count(),
+            percentage: value.percentage(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -59,7 +84,117 @@ pub struct WadItemSelectionUpdate {
     pub is_selected: bool,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum WadEditDto {
+    #[serde(rename_all = "camelCase")]
+    Write {
+        path_hash: String,
+        path: String,
+        source_path: String,
+        is_new: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Delete { path_hash: String, path: String },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WadInfoDto {
+    pub major: u8,
+    pub minor: u8,
+    /// Hex encoded signature
+    pub signature: String,
+    pub checksum: String,
+    pub toc_offset: u64,
+    pub file_size: u64,
+    pub chunk_count: usize,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    pub compression_histogram: HashMap<WadChunkCompressionDto, usize>,
+    /// Chunks with a compression type that isn't known
+    pub unknown_compression_count: usize,
+    pub duplicated_count: usize,
+    pub subchunked_count: usize,
+}
+
+/// A subchunk of a sub-chunked chunk, offsets are relative to the start of the chunk
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WadSubchunkDto {
+    pub index: usize,
+    pub compressed_offset: usize,
+    pub compressed_size: usize,
+    pub uncompressed_offset: usize,
+    pub uncompressed_size: usize,
+    pub checksum: String,
+}
+
+/// A window of the decompressed data of a chunk
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkBytesRangeResponse {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+    /// Decompressed size of the whole chunk
+    pub total_size: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractAnimationsResponse {
+    /// Names of the clips in the glb
+    pub clip_names: Vec<String>,
+    /// Animations which couldn't be converted
+    pub skipped_count: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractMapGeometryResponse {
+    pub mesh_count: usize,
+    /// More than one if the meshes were split across files
+    pub output_paths: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyWadResponse {
+    pub chunk_count: usize,
+    /// Whether the wad version has checksums which could be verified
+    pub is_checksum_verified: bool,
+    pub failures: Vec<WadChunkVerificationFailureDto>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WadChunkVerificationFailureDto {
+    pub path_hash: String,
+    pub path: String,
+    pub error: WadChunkVerificationErrorDto,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum WadChunkVerificationErrorDto {
+    #[serde(rename_all = "camelCase")]
+    ChecksumMismatch { expected: String, actual: String },
+    #[serde(rename_all = "camelCase")]
+    SizeMismatch { expected: usize, actual: usize },
+    #[serde(rename_all = "camelCase")]
+    LoadFailure { message: String },
+}
+
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FantomeInfoDto {
+    pub name: String,
+    pub author: String,
+    pub version: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WadChunkCompressionDto {
     None,
@@ -79,6 +214,8 @@ pub enum WadItemDto {
         path: String,
         name_hash: u64,
         path_hash: u64,
+        /// Hex path hash of the chunk, the numeric hashes lose precision in javascript
+        chunk_path_hash: String,
 
         compression_kind: WadChunkCompressionDto,
         compressed_size: usize,
@@ -119,6 +256,7 @@ impl From<&WadTreeFile> for WadItemDto {
             path: value.path().to_string(),
             name_hash: value.name_hash(),
             path_hash: value.path_hash(),
+            chunk_path_hash: format!("{:016x}", value.chunk().path_hash()),
             compression_kind: value.chunk().compression_type().into(),
             compressed_size: value.chunk().compressed_size(),
             uncompressed_size: value.chunk().uncompressed_size(),
@@ -156,6 +294,70 @@ impl From<WadChunkCompression> for WadChunkCompressionDto {
     }
 }
 
+impl From<&WadChunkVerificationFailure> for WadChunkVerificationFailureDto {
+    fn from(value: &WadChunkVerificationFailure) -> Self {
+        Self {
+            path_hash: format!("{:x}", value.path_hash),
+            path: value.path.to_string(),
+            error: match &value.error {
+                WadChunkVerificationError::ChecksumMismatch { expected, actual } => {
+                    WadChunkVerificationErrorDto::ChecksumMismatch {
+                        expected: format!("{:x}", expected),
+                        actual: format!("{:x}", actual),
+                    }
+                }
+                WadChunkVerificationError::SizeMismatch { expected, actual } => {
+                    WadChunkVerificationErrorDto::SizeMismatch {
+                        expected: *expected,
+                        actual: *actual,
+                    }
+                }
+                WadChunkVerificationError::LoadFailure { message } => {
+                    WadChunkVerificationErrorDto::LoadFailure {
+                        message: message.clone(),
+                    }
+                }
+            },
+        }
+    }
+}
+
+impl From<FantomeInfoDto> for FantomeInfo {
+    fn from(value: FantomeInfoDto) -> Self {
+        Self {
+            name: value.name,
+            author: value.author,
+            version: value.version,
+            description: value.description,
+        }
+    }
+}
+
+impl From<&WadSubchunk> for WadSubchunkDto {
+    fn from(value: &WadSubchunk) -> Self {
+        Self {
+            index: value.index,
+            compressed_offset: value.compressed_offset,
+            compressed_size: value.compressed_size,
+            uncompressed_offset: value.uncompressed_offset,
+            uncompressed_size: value.uncompressed_size,
+            checksum: format!("{:x}", value.checksum),
+        }
+    }
+}
+
+impl From<WadChunkCompressionDto> for WadChunkCompression {
+    fn from(value: WadChunkCompressionDto) -> Self {
+        match value {
+            WadChunkCompressionDto::None => Self::None,
+            WadChunkCompressionDto::GZip => Self::GZip,
+            WadChunkCompressionDto::Satellite => Self::Satellite,
+            WadChunkCompressionDto::Zstd => Self::Zstd,
+            WadChunkCompressionDto::ZstdMulti => Self::ZstdMulti,
+        }
+    }
+}
+
 fn guess_file_kind(file_name: impl AsRef<str>) -> LeagueFileKind {
     if let Some(extension) = Path::new(file_name.as_ref()).extension() {
         if let Some(extension) = extension.to_str() {
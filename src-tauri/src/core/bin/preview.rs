@@ -0,0 +1,217 @@
+use color_eyre::eyre::{self, Context};
+use indexmap::IndexMap;
+use league_toolkit::meta::{BinProperty, BinTree, BinTreeObject, PropertyValueEnum};
+use serde::Serialize;
+use std::io::Cursor;
+
+use crate::state::{BinHashKind, BinHashtable, WadHashtable};
+
+/// A property BIN file with every hash resolved to a name where one is known
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinPreview {
+    pub is_override: bool,
+    pub version: u32,
+    pub dependencies: Vec<String>,
+    pub entries: Vec<BinPreviewEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinPreviewEntry {
+    pub path: String,
+    pub class_name: String,
+    pub fields: Vec<BinPreviewField>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinPreviewField {
+    pub name: String,
+    pub value: BinPreviewValue,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinPreviewMapEntry {
+    pub key: BinPreviewValue,
+    pub value: BinPreviewValue,
+}
+
+/// The value of a BIN property, serialized as `{ "kind": ..., "value": ... }`
+///
+/// Hashes and links are resolved through the hashtables, unknown ones are written as hex.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "camelCase")]
+pub enum BinPreviewValue {
+    None,
+    Bool(bool),
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    U16(u16),
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    Vector2([f32; 2]),
+    Vector3([f32; 3]),
+    Vector4([f32; 4]),
+    /// Column major
+    Matrix44([f32; 16]),
+    /// RGBA
+    Color([u8; 4]),
+    String(String),
+    Hash(String),
+    WadChunkLink(String),
+    ObjectLink(String),
+    BitBool(bool),
+    Container(Vec<BinPreviewValue>),
+    UnorderedContainer(Vec<BinPreviewValue>),
+    #[serde(rename_all = "camelCase")]
+    Struct {
+        class_name: String,
+        fields: Vec<BinPreviewField>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Embedded {
+        class_name: String,
+        fields: Vec<BinPreviewField>,
+    },
+    Optional(Option<Box<BinPreviewValue>>),
+    Map(Vec<BinPreviewMapEntry>),
+}
+
+/// Parse a property BIN file and resolve its names through `bin_hashtable`, file links are
+/// resolved through `wad_hashtable`
+pub fn read_bin_preview(
+    data: &[u8],
+    bin_hashtable: &BinHashtable,
+    wad_hashtable: &WadHashtable,
+) -> eyre::Result<BinPreview> {
+    let tree = BinTree::from_reader(&mut Cursor::new(data)).wrap_err("failed to parse bin")?;
+
+    Ok(BinPreviewResolver {
+        bin_hashtable,
+        wad_hashtable,
+    }
+    .resolve_tree(&tree))
+}
+
+struct BinPreviewResolver<'a> {
+    bin_hashtable: &'a BinHashtable,
+    wad_hashtable: &'a WadHashtable,
+}
+
+impl BinPreviewResolver<'_> {
+    fn resolve_name(&self, kind: BinHashKind, hash: u32) -> String {
+        self.bin_hashtable.resolve(kind, hash).to_string()
+    }
+
+    fn resolve_tree(&self, tree: &BinTree) -> BinPreview {
+        BinPreview {
+            is_override: tree.is_override,
+            version: tree.version,
+            dependencies: tree.dependencies.clone(),
+            entries: tree
+                .objects
+                .values()
+                .map(|object| self.resolve_object(object))
+                .collect(),
+        }
+    }
+
+    fn resolve_object(&self, object: &BinTreeObject) -> BinPreviewEntry {
+        BinPreviewEntry {
+            path: self.resolve_name(BinHashKind::Entry, object.path_hash),
+            class_name: self.resolve_name(BinHashKind::Type, object.class_hash),
+            fields: self.resolve_properties(&object.properties),
+        }
+    }
+
+    fn resolve_properties(&self, properties: &IndexMap<u32, BinProperty>) -> Vec<BinPreviewField> {
+        properties
+            .values()
+            .map(|property| BinPreviewField {
+                name: self.resolve_name(BinHashKind::Field, property.name_hash),
+                value: self.resolve_value(&property.value),
+            })
+            .collect()
+    }
+
+    fn resolve_value(&self, value: &PropertyValueEnum) -> BinPreviewValue {
+        match value {
+            PropertyValueEnum::None(_) => BinPreviewValue::None,
+            PropertyValueEnum::Bool(value) => BinPreviewValue::Bool(value.0),
+            PropertyValueEnum::I8(value) => BinPreviewValue::I8(value.0),
+            PropertyValueEnum::U8(value) => BinPreviewValue::U8(value.0),
+            PropertyValueEnum::I16(value) => BinPreviewValue::I16(value.0),
+            PropertyValueEnum::U16(value) => BinPreviewValue::U16(value.0),
+            PropertyValueEnum::I32(value) => BinPreviewValue::I32(value.0),
+            PropertyValueEnum::U32(value) => BinPreviewValue::U32(value.0),
+            PropertyValueEnum::I64(value) => BinPreviewValue::I64(value.0),
+            PropertyValueEnum::U64(value) => BinPreviewValue::U64(value.0),
+            PropertyValueEnum::F32(value) => BinPreviewValue::F32(value.0),
+            PropertyValueEnum::Vector2(value) => BinPreviewValue::Vector2(value.0.to_array()),
+            PropertyValueEnum::Vector3(value) => BinPreviewValue::Vector3(value.0.to_array()),
+            PropertyValueEnum::Vector4(value) => BinPreviewValue::Vector4(value.0.to_array()),
+            PropertyValueEnum::Matrix44(value) => {
+                BinPreviewValue::Matrix44(value.0.to_cols_array())
+            }
+            PropertyValueEnum::Color(value) => {
+                BinPreviewValue::Color([value.0.r, value.0.g, value.0.b, value.0.a])
+            }
+            PropertyValueEnum::String(value) => BinPreviewValue::String(value.0.clone()),
+            PropertyValueEnum::Hash(value) => {
+                BinPreviewValue::Hash(self.resolve_name(BinHashKind::Hash, value.0))
+            }
+            PropertyValueEnum::WadChunkLink(value) => {
+                BinPreviewValue::WadChunkLink(self.wad_hashtable.resolve_path(value.0).to_string())
+            }
+            PropertyValueEnum::ObjectLink(value) => {
+                BinPreviewValue::ObjectLink(self.resolve_name(BinHashKind::Entry, value.0))
+            }
+            PropertyValueEnum::BitBool(value) => BinPreviewValue::BitBool(value.0),
+            PropertyValueEnum::Container(value) => BinPreviewValue::Container(
+                value
+                    .items
+                    .iter()
+                    .map(|item| self.resolve_value(item))
+                    .collect(),
+            ),
+            PropertyValueEnum::UnorderedContainer(value) => BinPreviewValue::UnorderedContainer(
+                value
+                    .0
+                    .items
+                    .iter()
+                    .map(|item| self.resolve_value(item))
+                    .collect(),
+            ),
+            PropertyValueEnum::Struct(value) => BinPreviewValue::Struct {
+                class_name: self.resolve_name(BinHashKind::Type, value.class_hash),
+                fields: self.resolve_properties(&value.properties),
+            },
+            PropertyValueEnum::Embedded(value) => BinPreviewValue::Embedded {
+                class_name: self.resolve_name(BinHashKind::Type, value.0.class_hash),
+                fields: self.resolve_properties(&value.0.properties),
+            },
+            PropertyValueEnum::Optional(value) => BinPreviewValue::Optional(
+                value
+                    .value
+                    .as_ref()
+                    .map(|value| Box::new(self.resolve_value(value))),
+            ),
+            PropertyValueEnum::Map(value) => BinPreviewValue::Map(
+                value
+                    .entries
+                    .iter()
+                    .map(|(key, value)| BinPreviewMapEntry {
+                        key: self.resolve_value(&key.0),
+                        value: self.resolve_value(value),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use color_eyre::eyre::{self, Context, eyre};
+use glam::Quat;
+use league_toolkit::anim::{AnimationAsset, RigResource};
+use serde_json::json;
+use std::{collections::HashMap, io::Cursor};
+
+use super::{
+    GlbBuilder, GlbRig, push_rig,
+    space::{mirror_quat, mirror_vec3},
+};
+use crate::core::hashes::hash_joint_name;
+
+/// Frame rate animations are sampled at when they don't specify one
+const DEFAULT_ANIMATION_FPS: f32 = 30.0;
+
+/// An animation (ANM) which gets converted to a clip
+#[derive(Debug, Clone)]
+pub struct AnimationClipSource<'a> {
+    pub name: String,
+    pub data: &'a [u8],
+}
+
+/// Convert animations to glb clips bound to the joints of a rig (SKL)
+///
+/// Animations which fail to convert are skipped, the names of the added clips are returned along
+/// with the glb.
+pub fn animations_to_glb(
+    rig_data: &[u8],
+    animations: &[AnimationClipSource],
+    report_progress: impl Fn(f64, Option<&str>) -> eyre::Result<()>,
+) -> eyre::Result<(Vec<u8>, Vec<String>)> {
+    let rig =
+        RigResource::from_reader(&mut Cursor::new(rig_data)).wrap_err("failed to parse rig")?;
+
+    let mut builder = GlbBuilder::new();
+    let glb_rig = push_rig(&mut builder, &rig)?;
+
+    let mut clip_names = Vec::with_capacity(animations.len());
+    for (i, animation) in animations.iter().enumerate() {
+        report_progress(i as f64 / animations.len() as f64, Some(&animation.name))?;
+
+        let asset = match AnimationAsset::from_reader(&mut Cursor::new(animation.data)) {
+            Ok(asset) => asset,
+            Err(error) => {
+                tracing::warn!(
+                    "skipping animation which failed to parse: {} ({})",
+                    animation.name,
+                    error
+                );
+                continue;
+            }
+        };
+
+        match push_animation(&mut builder, &rig, &glb_rig, &animation.name, &asset) {
+            Ok(_) => clip_names.push(animation.name.clone()),
+            Err(error) => tracing::warn!("skipping animation: {}", error),
+        }
+    }
+
+    if clip_names.is_empty() {
+        return Err(eyre!("none of the animations could be converted"));
+    }
+
+    Ok((builder.build(&glb_rig.root_nodes), clip_names))
+}
+
+/// Sample an animation at its frame rate and add it as a clip which animates the joint nodes of
+/// `glb_rig`, joints which aren't in the rig are ignored
+pub fn push_animation(
+    builder: &mut GlbBuilder,
+    rig: &RigResource,
+    glb_rig: &GlbRig,
+    name: &str,
+    asset: &AnimationAsset,
+) -> eyre::Result<usize> {
+    let (fps, duration) = match asset {
+        AnimationAsset::Compressed(animation) => (animation.fps(), animation.duration()),
+        AnimationAsset::Uncompressed(animation) => (animation.fps(), animation.duration()),
+    };
+    let fps = if fps > 0.0 {
+        fps
+    } else {
+        DEFAULT_ANIMATION_FPS
+    };
+
+    let frame_count = (duration * fps).round().max(0.0) as usize + 1;
+    let times = (0..frame_count)
+        .map(|frame| (frame as f32 / fps).min(duration.max(0.0)))
+        .collect::<Vec<_>>();
+
+    let joint_nodes = rig
+        .joints()
+        .iter()
+        .map(|joint| hash_joint_name(joint.name()))
+        .zip(glb_rig.joint_nodes.iter().copied())
+        .collect::<HashMap<_, _>>();
+
+    // (rotations, translations, scales) of every animated joint, one per frame
+    let mut tracks = HashMap::<u32, (Vec<[f32; 4]>, Vec<[f32; 3]>, Vec<[f32; 3]>)>::new();
+    for &time in &times {
+        let pose = match asset {
+            AnimationAsset::Compressed(animation) => animation.evaluate(time),
+            AnimationAsset::Uncompressed(animation) => animation.evaluate(time),
+        };
+
+        for (joint_hash, (rotation, translation, scale)) in pose {
+            if !joint_nodes.contains_key(&joint_hash) {
+                continue;
+            }
+
+            let (rotations, translations, scales) = tracks.entry(joint_hash).or_default();
+            rotations.push(continuous_rotation(rotations.last(), rotation));
+            translations.push(mirror_vec3(translation.to_array()));
+            scales.push(scale.to_array());
+        }
+    }
+
+    // joints missing from some frames can't be lined up with the times
+    let mut tracks = tracks
+        .into_iter()
+        .filter(|(_, (rotations, _, _))| rotations.len() == times.len())
+        .collect::<Vec<_>>();
+    if tracks.is_empty() {
+        return Err(eyre!(
+            "animation doesn't animate any joint of the rig: {}",
+            name
+        ));
+    }
+    tracks.sort_by_key(|(joint_hash, _)| joint_nodes[joint_hash]);
+
+    let input = builder.push_scalars(&times);
+
+    let mut samplers = Vec::new();
+    let mut channels = Vec::new();
+    for (joint_hash, (rotations, translations, scales)) in tracks {
+        let node = joint_nodes[&joint_hash];
+        for (path, output) in [
+            ("rotation", builder.push_vec4s(&rotations)),
+            ("translation", builder.push_vec3s(&translations)),
+            ("scale", builder.push_vec3s(&scales)),
+        ] {
+            channels.push(json!({
+                "sampler": samplers.len(),
+                "target": { "node": node, "path": path },
+            }));
+            samplers.push(json!({
+                "input": input,
+                "output": output,
+                "interpolation": "LINEAR",
+            }));
+        }
+    }
+
+    Ok(builder.push_animation(json!({
+        "name": name,
+        "samplers": samplers,
+        "channels": channels,
+    })))
+}
+
+/// Keep consecutive rotations in the same hemisphere so linear interpolation takes the short way
+fn continuous_rotation(previous: Option<&[f32; 4]>, rotation: Quat) -> [f32; 4] {
+    let rotation = mirror_quat(rotation.normalize().to_array());
+
+    match previous {
+        Some(previous)
+            if previous
+                .iter()
+                .zip(rotation.iter())
+                .map(|(a, b)| a * b)
+                .sum::<f32>()
+                < 0.0 =>
+        {
+            rotation.map(|x| -x)
+        }
+        _ => rotation,
+    }
+}
@@ -0,0 +1,281 @@
+use serde_json::{Map, Value, json};
+
+const GLB_MAGIC: u32 = 0x4654_6c67;
+const GLB_VERSION: u32 = 2;
+const GLB_JSON_CHUNK_TYPE: u32 = 0x4e4f_534a;
+const GLB_BIN_CHUNK_TYPE: u32 = 0x004e_4942;
+
+const COMPONENT_TYPE_UNSIGNED_BYTE: u32 = 5121;
+const COMPONENT_TYPE_UNSIGNED_SHORT: u32 = 5123;
+const COMPONENT_TYPE_FLOAT: u32 = 5126;
+
+const TARGET_ARRAY_BUFFER: u32 = 34962;
+const TARGET_ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Builds a binary glTF (`.glb`) file with a single buffer
+///
+/// Every `push_*` function returns the index of the pushed object, which is what other objects
+/// refer to it by.
+#[derive(Debug, Default)]
+pub struct GlbBuilder {
+    buffer: Vec<u8>,
+    buffer_views: Vec<Value>,
+    accessors: Vec<Value>,
+    images: Vec<Value>,
+    textures: Vec<Value>,
+    materials: Vec<Value>,
+    meshes: Vec<Value>,
+    nodes: Vec<Value>,
+    skins: Vec<Value>,
+    animations: Vec<Value>,
+}
+
+impl GlbBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_buffer_view(&mut self, data: &[u8], target: Option<u32>) -> usize {
+        // accessors require their data to be aligned to the component size
+        pad(&mut self.buffer, 0);
+
+        let mut buffer_view = json!({
+            "buffer": 0,
+            "byteOffset": self.buffer.len(),
+            "byteLength": data.len(),
+        });
+        if let Some(target) = target {
+            buffer_view["target"] = target.into();
+        }
+
+        self.buffer.extend_from_slice(data);
+        push(&mut self.buffer_views, buffer_view)
+    }
+
+    pub fn push_scalars(&mut self, data: &[f32]) -> usize {
+        let (min, max) = bounds(data.iter().map(|value| [*value]));
+        let values = data.iter().copied();
+        self.push_float_accessor(values, data.len(), "SCALAR", None, Some((&min, &max)))
+    }
+
+    pub fn push_vec2s(&mut self, data: &[[f32; 2]]) -> usize {
+        let values = data.iter().flatten().copied();
+        self.push_float_accessor(values, data.len(), "VEC2", Some(TARGET_ARRAY_BUFFER), None)
+    }
+
+    /// Animation output values
+    pub fn push_vec3s(&mut self, data: &[[f32; 3]]) -> usize {
+        self.push_vec3_accessor(data, None)
+    }
+
+    /// Vertex attributes
+    pub fn push_vertex_vec3s(&mut self, data: &[[f32; 3]]) -> usize {
+        self.push_vec3_accessor(data, Some(TARGET_ARRAY_BUFFER))
+    }
+
+    /// Animation output values
+    pub fn push_vec4s(&mut self, data: &[[f32; 4]]) -> usize {
+        self.push_vec4_accessor(data, None)
+    }
+
+    /// Vertex attributes
+    pub fn push_vertex_vec4s(&mut self, data: &[[f32; 4]]) -> usize {
+        self.push_vec4_accessor(data, Some(TARGET_ARRAY_BUFFER))
+    }
+
+    /// Column major 4x4 matrices
+    pub fn push_mat4s(&mut self, data: &[[f32; 16]]) -> usize {
+        let values = data.iter().flatten().copied();
+        self.push_float_accessor(values, data.len(), "MAT4", None, None)
+    }
+
+    pub fn push_joints(&mut self, data: &[[u8; 4]]) -> usize {
+        let bytes = data.iter().flatten().copied().collect::<Vec<_>>();
+        let buffer_view = self.push_buffer_view(&bytes, Some(TARGET_ARRAY_BUFFER));
+
+        push(
+            &mut self.accessors,
+            json!({
+                "bufferView": buffer_view,
+                "componentType": COMPONENT_TYPE_UNSIGNED_BYTE,
+                "count": data.len(),
+                "type": "VEC4",
+            }),
+        )
+    }
+
+    pub fn push_indices(&mut self, data: &[u16]) -> usize {
+        let bytes = data
+            .iter()
+            .flat_map(|index| index.to_le_bytes())
+            .collect::<Vec<_>>();
+        let buffer_view = self.push_buffer_view(&bytes, Some(TARGET_ELEMENT_ARRAY_BUFFER));
+
+        push(
+            &mut self.accessors,
+            json!({
+                "bufferView": buffer_view,
+                "componentType": COMPONENT_TYPE_UNSIGNED_SHORT,
+                "count": data.len(),
+                "type": "SCALAR",
+            }),
+        )
+    }
+
+    /// Embed an image, e.g. a PNG encoded texture
+    pub fn push_image(&mut self, data: &[u8], mime_type: &str) -> usize {
+        let buffer_view = self.push_buffer_view(data, None);
+        push(
+            &mut self.images,
+            json!({ "bufferView": buffer_view, "mimeType": mime_type }),
+        )
+    }
+
+    pub fn push_texture(&mut self, image: usize) -> usize {
+        push(&mut self.textures, json!({ "source": image }))
+    }
+
+    pub fn push_material(&mut self, material: Value) -> usize {
+        push(&mut self.materials, material)
+    }
+
+    pub fn push_mesh(&mut self, mesh: Value) -> usize {
+        push(&mut self.meshes, mesh)
+    }
+
+    pub fn push_node(&mut self, node: Value) -> usize {
+        push(&mut self.nodes, node)
+    }
+
+    pub fn node_mut(&mut self, node: usize) -> Option<&mut Value> {
+        self.nodes.get_mut(node)
+    }
+
+    pub fn push_skin(&mut self, skin: Value) -> usize {
+        push(&mut self.skins, skin)
+    }
+
+    pub fn push_animation(&mut self, animation: Value) -> usize {
+        push(&mut self.animations, animation)
+    }
+
+    /// Write the glb with a single scene made of `root_nodes`
+    pub fn build(self, root_nodes: &[usize]) -> Vec<u8> {
+        let mut document = Map::new();
+        document.insert(
+            "asset".into(),
+            json!({ "version": "2.0", "generator": "Obsidian" }),
+        );
+        document.insert("scene".into(), 0.into());
+        document.insert("scenes".into(), json!([{ "nodes": root_nodes }]));
+
+        // empty arrays aren't allowed by the spec
+        for (name, values) in [
+            ("bufferViews", self.buffer_views),
+            ("accessors", self.accessors),
+            ("images", self.images),
+            ("textures", self.textures),
+            ("materials", self.materials),
+            ("meshes", self.meshes),
+            ("nodes", self.nodes),
+            ("skins", self.skins),
+            ("animations", self.animations),
+        ] {
+            if !values.is_empty() {
+                document.insert(name.into(), Value::Array(values));
+            }
+        }
+
+        let mut buffer = self.buffer;
+        if !buffer.is_empty() {
+            document.insert("buffers".into(), json!([{ "byteLength": buffer.len() }]));
+        }
+
+        let mut document = Value::Object(document).to_string().into_bytes();
+        pad(&mut document, b' ');
+        pad(&mut buffer, 0);
+
+        let mut total_size = 12 + 8 + document.len();
+        if !buffer.is_empty() {
+            total_size += 8 + buffer.len();
+        }
+
+        let mut glb = Vec::with_capacity(total_size);
+        glb.extend_from_slice(&GLB_MAGIC.to_le_bytes());
+        glb.extend_from_slice(&GLB_VERSION.to_le_bytes());
+        glb.extend_from_slice(&(total_size as u32).to_le_bytes());
+
+        glb.extend_from_slice(&(document.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&GLB_JSON_CHUNK_TYPE.to_le_bytes());
+        glb.extend_from_slice(&document);
+
+        if !buffer.is_empty() {
+            glb.extend_from_slice(&(buffer.len() as u32).to_le_bytes());
+            glb.extend_from_slice(&GLB_BIN_CHUNK_TYPE.to_le_bytes());
+            glb.extend_from_slice(&buffer);
+        }
+
+        glb
+    }
+
+    /// Positions require bounds, so every VEC3 accessor gets them
+    fn push_vec3_accessor(&mut self, data: &[[f32; 3]], target: Option<u32>) -> usize {
+        let (min, max) = bounds(data.iter().copied());
+        let values = data.iter().flatten().copied();
+        self.push_float_accessor(values, data.len(), "VEC3", target, Some((&min, &max)))
+    }
+
+    fn push_vec4_accessor(&mut self, data: &[[f32; 4]], target: Option<u32>) -> usize {
+        let values = data.iter().flatten().copied();
+        self.push_float_accessor(values, data.len(), "VEC4", target, None)
+    }
+
+    fn push_float_accessor(
+        &mut self,
+        values: impl Iterator<Item = f32>,
+        count: usize,
+        accessor_type: &str,
+        target: Option<u32>,
+        bounds: Option<(&[f32], &[f32])>,
+    ) -> usize {
+        let bytes = values.flat_map(f32::to_le_bytes).collect::<Vec<_>>();
+        let buffer_view = self.push_buffer_view(&bytes, target);
+
+        let mut accessor = json!({
+            "bufferView": buffer_view,
+            "componentType": COMPONENT_TYPE_FLOAT,
+            "count": count,
+            "type": accessor_type,
+        });
+        if let Some((min, max)) = bounds.filter(|_| count > 0) {
+            accessor["min"] = json!(min);
+            accessor["max"] = json!(max);
+        }
+
+        push(&mut self.accessors, accessor)
+    }
+}
+
+fn push(values: &mut Vec<Value>, value: Value) -> usize {
+    values.push(value);
+    values.len() - 1
+}
+
+fn pad(data: &mut Vec<u8>, byte: u8) {
+    while !data.len().is_multiple_of(4) {
+        data.push(byte);
+    }
+}
+
+fn bounds<const N: usize>(values: impl Iterator<Item = [f32; N]>) -> ([f32; N], [f32; N]) {
+    values.fold(
+        ([f32::INFINITY; N], [f32::NEG_INFINITY; N]),
+        |(mut min, mut max), value| {
+            for i in 0..N {
+                min[i] = min[i].min(value[i]);
+                max[i] = max[i].max(value[i]);
+            }
+            (min, max)
+        },
+    )
+}
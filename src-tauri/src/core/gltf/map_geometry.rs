@@ -0,0 +1,158 @@
+use color_eyre::eyre::{self, eyre};
+use serde_json::json;
+use std::collections::HashMap;
+
+use super::{
+    GlbBuilder,
+    space::{flip_winding, mirror_mat4, mirror_vec3},
+};
+use crate::core::mapgeo::{MapGeometry, MapGeometryMesh};
+
+/// Glbs converted from map geometry
+#[derive(Debug, Clone)]
+pub struct MapGeometryGlbs {
+    pub glbs: Vec<Vec<u8>>,
+    /// Amount of meshes which were converted, meshes which fail to convert are skipped
+    pub mesh_count: usize,
+}
+
+/// Convert map geometry to glbs, every mesh becomes a node with a primitive per submesh and
+/// materials named after the ones of the submeshes
+///
+/// With `max_meshes_per_file` the meshes are split across multiple glbs, otherwise all of them go
+/// into one. `load_texture` gets the name of a material and returns the PNG of its diffuse
+/// texture, if it could be found. Fails if none of the meshes could be converted.
+pub fn map_geometry_to_glbs(
+    geometry: &MapGeometry,
+    max_meshes_per_file: Option<usize>,
+    mut load_texture: impl FnMut(&str) -> Option<Vec<u8>>,
+    report_progress: impl Fn(f64, Option<&str>) -> eyre::Result<()>,
+) -> eyre::Result<MapGeometryGlbs> {
+    if geometry.meshes.is_empty() {
+        return Err(eyre!("map geometry has no meshes"));
+    }
+
+    let chunk_size = max_meshes_per_file
+        .filter(|&size| size > 0)
+        .unwrap_or(geometry.meshes.len());
+
+    // textures are shared by many meshes, so they only get decoded once
+    let mut textures = HashMap::<String, Option<Vec<u8>>>::new();
+    let mut glbs = Vec::new();
+    let mut mesh_count = 0;
+    for (i, meshes) in geometry.meshes.chunks(chunk_size).enumerate() {
+        report_progress(
+            (i * chunk_size) as f64 / geometry.meshes.len() as f64,
+            Some(&format!("Converting meshes {}...", i * chunk_size + 1)),
+        )?;
+
+        let mut builder = GlbBuilder::new();
+        let mut materials = HashMap::new();
+        let mut nodes = Vec::with_capacity(meshes.len());
+        for mesh in meshes {
+            let mut material = |builder: &mut GlbBuilder, name: &str| {
+                *materials.entry(name.to_string()).or_insert_with(|| {
+                    let texture = textures
+                        .entry(name.to_string())
+                        .or_insert_with(|| load_texture(name));
+
+                    push_map_material(builder, name, texture.as_deref())
+                })
+            };
+
+            match push_map_geometry_mesh(&mut builder, mesh, &mut material) {
+                Ok(node) => nodes.push(node),
+                Err(error) => tracing::warn!("skipping mesh {}: {}", mesh.name, error),
+            }
+        }
+
+        if nodes.is_empty() {
+            continue;
+        }
+
+        mesh_count += nodes.len();
+        glbs.push(builder.build(&nodes));
+    }
+
+    if glbs.is_empty() {
+        return Err(eyre!("none of the map geometry meshes could be converted"));
+    }
+
+    Ok(MapGeometryGlbs { glbs, mesh_count })
+}
+
+/// Add a mesh of a map as a node, `material` adds or reuses the material of a submesh
+pub fn push_map_geometry_mesh(
+    builder: &mut GlbBuilder,
+    mesh: &MapGeometryMesh,
+    material: &mut impl FnMut(&mut GlbBuilder, &str) -> usize,
+) -> eyre::Result<usize> {
+    let positions = mesh
+        .positions
+        .iter()
+        .map(|position| mirror_vec3(*position))
+        .collect::<Vec<_>>();
+
+    let mut attributes = json!({ "POSITION": builder.push_vertex_vec3s(&positions) });
+
+    if let Some(normals) = &mesh.normals {
+        let normals = normals
+            .iter()
+            .map(|normal| mirror_vec3(*normal))
+            .collect::<Vec<_>>();
+        attributes["NORMAL"] = builder.push_vertex_vec3s(&normals).into();
+    }
+
+    if let Some(uvs) = &mesh.uvs {
+        attributes["TEXCOORD_0"] = builder.push_vec2s(uvs).into();
+    }
+
+    let mut primitives = Vec::new();
+    for submesh in &mesh.submeshes {
+        let start = submesh.start_index as usize;
+        let end = start + submesh.index_count as usize;
+        let Some(indices) = mesh.indices.get(start..end) else {
+            tracing::warn!(
+                "skipping submesh with invalid index range: {}",
+                submesh.material
+            );
+            continue;
+        };
+
+        let mut indices = indices.to_vec();
+        flip_winding(&mut indices);
+
+        primitives.push(json!({
+            "attributes": attributes,
+            "indices": builder.push_indices(&indices),
+            "material": material(builder, &submesh.material),
+        }));
+    }
+
+    if primitives.is_empty() {
+        return Err(eyre!("mesh has no submeshes"));
+    }
+
+    let gltf_mesh = builder.push_mesh(json!({ "name": mesh.name, "primitives": primitives }));
+
+    Ok(builder.push_node(json!({
+        "name": mesh.name,
+        "mesh": gltf_mesh,
+        "matrix": mirror_mat4(mesh.transform),
+    })))
+}
+
+fn push_map_material(builder: &mut GlbBuilder, name: &str, texture: Option<&[u8]>) -> usize {
+    let mut material = json!({
+        "name": name,
+        "pbrMetallicRoughness": { "metallicFactor": 0.0 },
+    });
+
+    if let Some(texture) = texture {
+        let image = builder.push_image(texture, "image/png");
+        let texture = builder.push_texture(image);
+        material["pbrMetallicRoughness"]["baseColorTexture"] = json!({ "index": texture });
+    }
+
+    builder.push_material(material)
+}
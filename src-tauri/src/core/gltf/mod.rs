@@ -0,0 +1,12 @@
+mod animation;
+mod glb;
+mod map_geometry;
+mod rig;
+mod skinned_mesh;
+mod space;
+
+pub use animation::*;
+pub use glb::*;
+pub use map_geometry::*;
+pub use rig::*;
+pub use skinned_mesh::*;
@@ -0,0 +1,106 @@
+use color_eyre::eyre::{self, eyre};
+use league_toolkit::anim::RigResource;
+use serde_json::json;
+
+use super::{
+    GlbBuilder,
+    space::{mirror_mat4, mirror_quat, mirror_vec3},
+};
+
+/// Nodes of a rig which was added to a glb
+#[derive(Debug, Clone)]
+pub struct GlbRig {
+    /// Node of every joint, in the order of the joints of the rig
+    pub joint_nodes: Vec<usize>,
+    /// Joints without a parent
+    pub root_nodes: Vec<usize>,
+    pub skin: usize,
+}
+
+impl GlbRig {
+    /// Find the node of a joint by its name
+    pub fn find_joint_node(&self, rig: &RigResource, name: &str) -> Option<usize> {
+        rig.joints()
+            .iter()
+            .position(|joint| joint.name() == name)
+            .map(|index| self.joint_nodes[index])
+    }
+}
+
+/// Add the joints of a rig as a node hierarchy, along with a skin which binds vertices to them
+///
+/// Skinned meshes refer to joints through the influences of the rig, so those make up the joints
+/// of the skin. Rigs without influences bind every joint in order.
+pub fn push_rig(builder: &mut GlbBuilder, rig: &RigResource) -> eyre::Result<GlbRig> {
+    let joints = rig.joints();
+    if joints.is_empty() {
+        return Err(eyre!("rig has no joints"));
+    }
+
+    let joint_nodes = joints
+        .iter()
+        .map(|joint| {
+            builder.push_node(json!({
+                "name": joint.name(),
+                "translation": mirror_vec3(joint.local_translation().to_array()),
+                "rotation": mirror_quat(joint.local_rotation().to_array()),
+                "scale": joint.local_scale().to_array(),
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    let mut root_nodes = Vec::new();
+    for (index, joint) in joints.iter().enumerate() {
+        let parent = usize::try_from(joint.parent_id())
+            .ok()
+            .filter(|&parent| parent < joints.len() && parent != index);
+
+        let Some(parent) = parent else {
+            root_nodes.push(joint_nodes[index]);
+            continue;
+        };
+
+        if let Some(parent_node) = builder.node_mut(joint_nodes[parent]) {
+            match parent_node["children"].as_array_mut() {
+                Some(children) => children.push(joint_nodes[index].into()),
+                None => parent_node["children"] = json!([joint_nodes[index]]),
+            }
+        }
+    }
+
+    let skin_joints = match rig.influences().is_empty() {
+        true => (0..joints.len()).collect::<Vec<_>>(),
+        false => rig
+            .influences()
+            .iter()
+            .map(|&influence| {
+                usize::try_from(influence)
+                    .ok()
+                    .filter(|&joint| joint < joints.len())
+                    .unwrap_or_default()
+            })
+            .collect(),
+    };
+
+    let inverse_bind_matrices = skin_joints
+        .iter()
+        .map(|&joint| mirror_mat4(joints[joint].inverse_bind_transform().to_cols_array()))
+        .collect::<Vec<_>>();
+    let inverse_bind_matrices = builder.push_mat4s(&inverse_bind_matrices);
+
+    let mut skin = json!({
+        "name": rig.name(),
+        "inverseBindMatrices": inverse_bind_matrices,
+        "joints": skin_joints.iter().map(|&joint| joint_nodes[joint]).collect::<Vec<_>>(),
+    });
+    if let Some(root_node) = root_nodes.first() {
+        skin["skeleton"] = (*root_node).into();
+    }
+    let skin = builder.push_skin(skin);
+
+    Ok(GlbRig {
+        joint_nodes,
+        root_nodes,
+        skin,
+    })
+}
@@ -0,0 +1,140 @@
+use color_eyre::eyre::{self, Context, ContextCompat, eyre};
+use glam::{Vec2, Vec3, Vec4};
+use league_toolkit::{
+    anim::RigResource,
+    mesh::{SkinnedMesh, mem::vertex::ElementName},
+};
+use serde_json::json;
+use std::{collections::HashMap, io::Cursor};
+
+use super::{
+    GlbBuilder, GlbRig, push_rig,
+    space::{flip_winding, mirror_vec3},
+};
+
+/// Convert a skinned mesh (SKN) to a glb, every submesh becomes a primitive with a material named
+/// after the one of the submesh
+///
+/// With a rig (SKL) the joints are added as nodes and the mesh gets skinned to them.
+pub fn skinned_mesh_to_glb(mesh_data: &[u8], rig_data: Option<&[u8]>) -> eyre::Result<Vec<u8>> {
+    let mesh = SkinnedMesh::from_reader(&mut Cursor::new(mesh_data))
+        .wrap_err("failed to parse skinned mesh")?;
+    let rig = rig_data
+        .map(|rig_data| RigResource::from_reader(&mut Cursor::new(rig_data)))
+        .transpose()
+        .wrap_err("failed to parse rig")?;
+
+    let mut builder = GlbBuilder::new();
+    let rig_nodes = rig
+        .as_ref()
+        .map(|rig| push_rig(&mut builder, rig))
+        .transpose()?;
+
+    let mesh_node = push_skinned_mesh(&mut builder, &mesh, rig_nodes.as_ref())?;
+
+    let mut root_nodes = vec![mesh_node];
+    if let Some(rig_nodes) = &rig_nodes {
+        root_nodes.extend_from_slice(&rig_nodes.root_nodes);
+    }
+
+    Ok(builder.build(&root_nodes))
+}
+
+/// Add a skinned mesh as a node, skinned to `rig` if there is one
+pub fn push_skinned_mesh(
+    builder: &mut GlbBuilder,
+    mesh: &SkinnedMesh,
+    rig: Option<&GlbRig>,
+) -> eyre::Result<usize> {
+    let vertex_buffer = mesh.vertex_buffer();
+
+    let positions = vertex_buffer
+        .accessor::<Vec3>(ElementName::Position)
+        .wrap_err("mesh has no positions")?
+        .iter()
+        .map(|position| mirror_vec3(position.to_array()))
+        .collect::<Vec<_>>();
+
+    let mut attributes = json!({ "POSITION": builder.push_vertex_vec3s(&positions) });
+
+    if let Some(normals) = vertex_buffer.accessor::<Vec3>(ElementName::Normal) {
+        let normals = normals
+            .iter()
+            .map(|normal| mirror_vec3(normal.normalize_or_zero().to_array()))
+            .collect::<Vec<_>>();
+        attributes["NORMAL"] = builder.push_vertex_vec3s(&normals).into();
+    }
+
+    if let Some(uvs) = vertex_buffer.accessor::<Vec2>(ElementName::Texcoord0) {
+        let uvs = uvs.iter().map(|uv| uv.to_array()).collect::<Vec<_>>();
+        attributes["TEXCOORD_0"] = builder.push_vec2s(&uvs).into();
+    }
+
+    if rig.is_some() {
+        let joints = vertex_buffer
+            .accessor::<[u8; 4]>(ElementName::BlendIndex)
+            .wrap_err("mesh has no blend indices")?
+            .iter()
+            .collect::<Vec<_>>();
+        let weights = vertex_buffer
+            .accessor::<Vec4>(ElementName::BlendWeight)
+            .wrap_err("mesh has no blend weights")?
+            .iter()
+            .map(normalize_weights)
+            .collect::<Vec<_>>();
+
+        attributes["JOINTS_0"] = builder.push_joints(&joints).into();
+        attributes["WEIGHTS_0"] = builder.push_vertex_vec4s(&weights).into();
+    }
+
+    let indices = mesh.index_buffer().iter().collect::<Vec<u16>>();
+
+    let mut materials = HashMap::new();
+    let mut primitives = Vec::new();
+    for range in mesh.ranges() {
+        let start = range.start_index as usize;
+        let end = start + range.index_count as usize;
+        let Some(range_indices) = indices.get(start..end) else {
+            tracing::warn!(
+                "skipping submesh with invalid index range: {}",
+                range.material
+            );
+            continue;
+        };
+
+        let mut range_indices = range_indices.to_vec();
+        flip_winding(&mut range_indices);
+
+        let material = *materials
+            .entry(range.material.clone())
+            .or_insert_with(|| builder.push_material(json!({ "name": range.material })));
+
+        primitives.push(json!({
+            "attributes": attributes,
+            "indices": builder.push_indices(&range_indices),
+            "material": material,
+        }));
+    }
+
+    if primitives.is_empty() {
+        return Err(eyre!("mesh has no submeshes"));
+    }
+
+    let mesh = builder.push_mesh(json!({ "primitives": primitives }));
+
+    let mut node = json!({ "name": "mesh", "mesh": mesh });
+    if let Some(rig) = rig {
+        node["skin"] = rig.skin.into();
+    }
+
+    Ok(builder.push_node(node))
+}
+
+/// glTF requires the weights of a vertex to add up to 1
+fn normalize_weights(weights: Vec4) -> [f32; 4] {
+    let sum = weights.element_sum();
+    match sum > 0.0 {
+        true => (weights / sum).to_array(),
+        false => [1.0, 0.0, 0.0, 0.0],
+    }
+}
@@ -0,0 +1,30 @@
+/// League uses a left handed coordinate system and glTF a right handed one, so everything gets
+/// mirrored along the X axis on the way out
+pub fn mirror_vec3([x, y, z]: [f32; 3]) -> [f32; 3] {
+    [-x, y, z]
+}
+
+/// Quaternions are stored as `[x, y, z, w]`
+pub fn mirror_quat([x, y, z, w]: [f32; 4]) -> [f32; 4] {
+    [x, -y, -z, w]
+}
+
+/// Column major matrices, the result is `S * M * S` where `S` mirrors the X axis
+pub fn mirror_mat4(mut matrix: [f32; 16]) -> [f32; 16] {
+    for column in 0..4 {
+        for row in 0..4 {
+            if (column == 0) != (row == 0) {
+                matrix[column * 4 + row] = -matrix[column * 4 + row];
+            }
+        }
+    }
+
+    matrix
+}
+
+/// Mirroring flips the winding order of triangles, so it has to be flipped back
+pub fn flip_winding(indices: &mut [u16]) {
+    for triangle in indices.chunks_exact_mut(3) {
+        triangle.swap(1, 2);
+    }
+}
@@ -0,0 +1,359 @@
+use color_eyre::eyre::{self, eyre};
+use memmap2::Mmap;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    ops::Deref,
+    path::Path,
+    time::UNIX_EPOCH,
+};
+
+use super::{open_hashtable_file, parse_hashtable};
+
+/// Directory inside of the hashtables directory which holds the caches
+pub const HASHTABLE_CACHE_DIR: &str = ".cache";
+pub const HASHTABLE_CACHE_EXTENSION: &str = "bin";
+
+const HASHTABLE_CACHE_MAGIC: &[u8; 4] = b"OHTC";
+const HASHTABLE_CACHE_VERSION: u32 = 3;
+const HASHTABLE_CACHE_HEADER_SIZE: usize = 48;
+const HASHTABLE_CACHE_HASH_SIZE: usize = 8;
+const HASHTABLE_CACHE_SPAN_SIZE: usize = 8;
+
+/// Amount of conflicts which get logged individually per file
+const MAX_LOGGED_CONFLICTS: usize = 10;
+
+/// Binary cache of a single hashtable file, memory-mapped from disk
+///
+/// Paths are stored in a single blob and looked up by binary search over the sorted hashes,
+/// which takes a fraction of the memory of a map with a separate allocation per path.
+///
+/// Layout (little endian):
+/// - header: magic, version, source size, source mtime (nanoseconds), entry count, invalid line
+///   count, conflict count
+/// - sorted path hashes (`u64`)
+/// - path spans (`u32` offset and `u32` length into the path blob), in hash order
+/// - path blob (utf-8)
+#[derive(Debug)]
+pub struct HashtableCache {
+    data: HashtableCacheData,
+    count: usize,
+    invalid_line_count: usize,
+    conflict_count: usize,
+}
+
+/// Either the mapped cache file, or the same layout built in memory when there's no cache file
+#[derive(Debug)]
+enum HashtableCacheData {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+/// Size and modification time of a hashtable file, the cache is rebuilt when either changes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct HashtableSourceStamp {
+    size: u64,
+    modified: u64,
+}
+
+/// Entries of a parsed hashtable file, sorted by hash and without duplicates
+struct ParsedHashtable {
+    entries: Vec<(u64, u32, u32)>,
+    blob: Vec<u8>,
+    invalid_line_count: usize,
+    conflict_count: usize,
+}
+
+impl HashtableCache {
+    /// Open the cache of `source_path`, building it first if it's missing or out of date
+    ///
+    /// If the cache file can't be written, the cache is kept in memory instead.
+    pub fn load_or_build(
+        source_path: impl AsRef<Path>,
+        cache_path: impl AsRef<Path>,
+    ) -> eyre::Result<Self> {
+        let source_path = source_path.as_ref();
+        let cache_path = cache_path.as_ref();
+        let stamp = HashtableSourceStamp::read(source_path)?;
+
+        match Self::open(cache_path, stamp) {
+            Ok(Some(cache)) => return Ok(cache),
+            Ok(None) => {}
+            Err(error) => tracing::warn!(
+                "failed to open hashtable cache: {} ({:?})",
+                cache_path.display(),
+                error
+            ),
+        }
+
+        tracing::info!(
+            "building hashtable cache: {} -> {}",
+            source_path.display(),
+            cache_path.display()
+        );
+        let parsed = ParsedHashtable::parse(source_path)?;
+        if let Err(error) = parsed.write_file(cache_path, stamp) {
+            tracing::warn!(
+                "failed to write hashtable cache, keeping it in memory: {} ({:?})",
+                cache_path.display(),
+                error
+            );
+            return parsed.into_cache(stamp);
+        }
+
+        Self::open(cache_path, stamp)?.ok_or(eyre!(
+            "hashtable cache is invalid after building it: {}",
+            cache_path.display()
+        ))
+    }
+
+    /// Build the cache of `source_path` in memory, without a cache file
+    pub fn from_source(source_path: impl AsRef<Path>) -> eyre::Result<Self> {
+        ParsedHashtable::parse(source_path.as_ref())?.into_cache(HashtableSourceStamp::default())
+    }
+
+    /// Returns `None` if the cache doesn't exist or was built from a different source file
+    fn open(cache_path: &Path, stamp: HashtableSourceStamp) -> eyre::Result<Option<Self>> {
+        let file = match File::open(cache_path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        // SAFETY: cache files are only written by `ParsedHashtable::write_file`, which replaces
+        // them atomically instead of modifying them in place
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        Self::from_data(HashtableCacheData::Mapped(mmap), stamp)
+    }
+
+    fn from_data(
+        data: HashtableCacheData,
+        stamp: HashtableSourceStamp,
+    ) -> eyre::Result<Option<Self>> {
+        if data.len() < HASHTABLE_CACHE_HEADER_SIZE
+            || &data[0..4] != HASHTABLE_CACHE_MAGIC
+            || read_u32(&data, 4) != HASHTABLE_CACHE_VERSION
+        {
+            return Ok(None);
+        }
+
+        let cached_stamp = HashtableSourceStamp {
+            size: read_u64(&data, 8),
+            modified: read_u64(&data, 16),
+        };
+        if cached_stamp != stamp {
+            return Ok(None);
+        }
+
+        let count = read_u64(&data, 24) as usize;
+        let blob_offset = count
+            .checked_mul(HASHTABLE_CACHE_HASH_SIZE + HASHTABLE_CACHE_SPAN_SIZE)
+            .and_then(|size| size.checked_add(HASHTABLE_CACHE_HEADER_SIZE));
+        if blob_offset.is_none_or(|blob_offset| blob_offset > data.len()) {
+            return Err(eyre!("truncated hashtable cache (count: {})", count));
+        }
+
+        Ok(Some(Self {
+            invalid_line_count: read_u64(&data, 32) as usize,
+            conflict_count: read_u64(&data, 40) as usize,
+            data,
+            count,
+        }))
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.count
+    }
+
+    /// Amount of malformed lines which were skipped in the source file
+    pub fn invalid_line_count(&self) -> usize {
+        self.invalid_line_count
+    }
+
+    /// Amount of hashes which appear multiple times in the source file with different paths
+    pub fn conflict_count(&self) -> usize {
+        self.conflict_count
+    }
+
+    pub fn get(&self, path_hash: u64) -> Option<&str> {
+        let mut low = 0;
+        let mut high = self.count;
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.hash(middle).cmp(&path_hash) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return self.path(middle),
+            }
+        }
+
+        None
+    }
+
+    fn hash(&self, index: usize) -> u64 {
+        read_u64(
+            &self.data,
+            HASHTABLE_CACHE_HEADER_SIZE + index * HASHTABLE_CACHE_HASH_SIZE,
+        )
+    }
+
+    fn path(&self, index: usize) -> Option<&str> {
+        let span_offset = HASHTABLE_CACHE_HEADER_SIZE
+            + self.count * HASHTABLE_CACHE_HASH_SIZE
+            + index * HASHTABLE_CACHE_SPAN_SIZE;
+        let blob_offset = HASHTABLE_CACHE_HEADER_SIZE
+            + self.count * (HASHTABLE_CACHE_HASH_SIZE + HASHTABLE_CACHE_SPAN_SIZE);
+
+        let offset = blob_offset + read_u32(&self.data, span_offset) as usize;
+        let length = read_u32(&self.data, span_offset + 4) as usize;
+
+        std::str::from_utf8(self.data.get(offset..offset + length)?).ok()
+    }
+}
+
+impl ParsedHashtable {
+    fn parse(source_path: &Path) -> eyre::Result<Self> {
+        let mut blob = Vec::<u8>::new();
+        let mut entries = Vec::<(u64, u32, u32)>::new();
+        let mut is_too_large = false;
+        let stats = parse_hashtable(open_hashtable_file(source_path)?, |hash, path| {
+            let Ok(offset) = u32::try_from(blob.len()) else {
+                is_too_large = true;
+                return;
+            };
+
+            blob.extend_from_slice(path.as_bytes());
+            entries.push((hash, offset, path.len() as u32));
+        })?;
+        if is_too_large {
+            return Err(eyre!("hashtable is too large"));
+        }
+
+        // later lines override earlier ones, same as inserting them into a map
+        entries.reverse();
+        entries.sort_by_key(|(hash, _, _)| *hash);
+        let conflict_count = count_conflicts(&entries, &blob);
+        entries.dedup_by_key(|(hash, _, _)| *hash);
+
+        Ok(Self {
+            entries,
+            blob,
+            invalid_line_count: stats.invalid_line_count,
+            conflict_count,
+        })
+    }
+
+    fn write(&self, writer: &mut impl Write, stamp: HashtableSourceStamp) -> io::Result<()> {
+        writer.write_all(HASHTABLE_CACHE_MAGIC)?;
+        writer.write_all(&HASHTABLE_CACHE_VERSION.to_le_bytes())?;
+        writer.write_all(&stamp.size.to_le_bytes())?;
+        writer.write_all(&stamp.modified.to_le_bytes())?;
+        writer.write_all(&(self.entries.len() as u64).to_le_bytes())?;
+        writer.write_all(&(self.invalid_line_count as u64).to_le_bytes())?;
+        writer.write_all(&(self.conflict_count as u64).to_le_bytes())?;
+        for (hash, _, _) in &self.entries {
+            writer.write_all(&hash.to_le_bytes())?;
+        }
+        for (_, offset, length) in &self.entries {
+            writer.write_all(&offset.to_le_bytes())?;
+            writer.write_all(&length.to_le_bytes())?;
+        }
+        writer.write_all(&self.blob)?;
+
+        Ok(())
+    }
+
+    fn write_file(&self, cache_path: &Path, stamp: HashtableSourceStamp) -> eyre::Result<()> {
+        if let Some(cache_directory) = cache_path.parent() {
+            fs::create_dir_all(cache_directory)?;
+        }
+
+        // write into a temporary file first so a failed build never leaves a valid looking cache
+        let temporary_path = cache_path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&temporary_path)?);
+        self.write(&mut writer, stamp)?;
+        writer.into_inner()?.sync_all()?;
+
+        fs::rename(&temporary_path, cache_path)?;
+
+        tracing::info!(
+            "built hashtable cache (entry_count: {})",
+            self.entries.len()
+        );
+
+        Ok(())
+    }
+
+    fn into_cache(self, stamp: HashtableSourceStamp) -> eyre::Result<HashtableCache> {
+        let mut data = Vec::with_capacity(
+            HASHTABLE_CACHE_HEADER_SIZE
+                + self.entries.len() * (HASHTABLE_CACHE_HASH_SIZE + HASHTABLE_CACHE_SPAN_SIZE)
+                + self.blob.len(),
+        );
+        self.write(&mut data, stamp)?;
+
+        HashtableCache::from_data(HashtableCacheData::Owned(data), stamp)?
+            .ok_or(eyre!("hashtable cache is invalid after building it"))
+    }
+}
+
+impl Deref for HashtableCacheData {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Mapped(mmap) => mmap,
+            Self::Owned(data) => data,
+        }
+    }
+}
+
+impl HashtableSourceStamp {
+    fn read(source_path: &Path) -> eyre::Result<Self> {
+        let metadata = fs::metadata(source_path)?;
+
+        Ok(Self {
+            size: metadata.len(),
+            modified: metadata
+                .modified()?
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_nanos() as u64)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Count the hashes which have entries with different paths, `entries` has to be sorted by hash
+fn count_conflicts(entries: &[(u64, u32, u32)], blob: &[u8]) -> usize {
+    let path = |(_, offset, length): &(u64, u32, u32)| {
+        &blob[*offset as usize..*offset as usize + *length as usize]
+    };
+
+    let mut conflict_count = 0;
+    for group in entries.chunk_by(|a, b| a.0 == b.0) {
+        if group.iter().any(|entry| path(entry) != path(&group[0])) {
+            if conflict_count < MAX_LOGGED_CONFLICTS {
+                tracing::warn!(
+                    "conflicting hashtable paths (path_hash: {:016x}): {:?}",
+                    group[0].0,
+                    group
+                        .iter()
+                        .map(|entry| String::from_utf8_lossy(path(entry)))
+                        .collect::<Vec<_>>()
+                );
+            }
+            conflict_count += 1;
+        }
+    }
+
+    conflict_count
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
@@ -0,0 +1,299 @@
+use color_eyre::eyre;
+use league_toolkit::{
+    file::LeagueFileKind,
+    wad::{WadChunk, WadDecoder},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, OpenOptions},
+    io::{BufWriter, Read, Seek, Write},
+    path::Path,
+};
+
+use crate::core::wad::hash_chunk_path;
+
+/// File kinds which reference other files by path
+const SCANNED_FILE_KINDS: &[LeagueFileKind] = &[
+    LeagueFileKind::PropertyBin,
+    LeagueFileKind::PropertyBinOverride,
+    LeagueFileKind::SimpleSkin,
+    LeagueFileKind::Preload,
+    LeagueFileKind::LuaObj,
+];
+
+/// Extensions tried for every discovered path
+const VARIANT_EXTENSIONS: &[&str] = &[
+    "bin",
+    "dds",
+    "tex",
+    "png",
+    "jpg",
+    "tga",
+    "svg",
+    "skn",
+    "skl",
+    "anm",
+    "scb",
+    "sco",
+    "bnk",
+    "wpk",
+    "json",
+    "txt",
+    "lua",
+    "luaobj",
+    "preload",
+    "mapgeo",
+    "stringtable",
+];
+
+const VARIANT_LOCALES: &[&str] = &[
+    "en_us", "en_gb", "en_au", "en_ph", "en_sg", "ar_ae", "cs_cz", "de_de", "el_gr", "es_ar",
+    "es_es", "es_mx", "fr_fr", "hu_hu", "id_id", "it_it", "ja_jp", "ko_kr", "pl_pl", "pt_br",
+    "ro_ro", "ru_ru", "th_th", "tr_tr", "vi_vn", "zh_cn", "zh_my", "zh_tw",
+];
+
+const VARIANT_ROOTS: &[&str] = &["assets", "data"];
+
+/// Skin numbers tried for paths containing `skin<number>`
+const MAX_VARIANT_SKIN: u32 = 99;
+
+const MIN_PATH_LENGTH: usize = 5;
+const MAX_EXTENSION_LENGTH: usize = 12;
+/// Amount of bytes checked when guessing whether a chunk is text
+const TEXT_SNIFF_SIZE: usize = 1024;
+
+/// Finds paths of unresolved chunks by mining path-like strings out of chunk contents and
+/// hashing them, along with variants of them
+#[derive(Debug, Default)]
+pub struct HashDiscovery {
+    unresolved: HashSet<u64>,
+    tried_paths: HashSet<String>,
+    discovered: HashMap<u64, String>,
+}
+
+impl HashDiscovery {
+    pub fn new(unresolved: HashSet<u64>) -> Self {
+        Self {
+            unresolved,
+            ..Default::default()
+        }
+    }
+
+    /// Scan the chunks of a wad, returns the amount of chunks which were scanned
+    pub fn scan_wad_chunks<TSource: Read + Seek>(
+        &mut self,
+        decoder: &mut WadDecoder<TSource>,
+        chunks: &HashMap<u64, WadChunk>,
+        report_progress: impl Fn(f64) -> eyre::Result<()>,
+    ) -> eyre::Result<usize> {
+        let mut scanned_chunk_count = 0;
+
+        for (i, chunk) in chunks.values().enumerate() {
+            if self.unresolved.is_empty() {
+                break;
+            }
+
+            report_progress(i as f64 / chunks.len() as f64)?;
+
+            let data = match decoder.load_chunk_decompressed(chunk) {
+                Ok(data) => data,
+                Err(error) => {
+                    tracing::warn!(
+                        "failed to decompress chunk (path_hash: {:x}): {}",
+                        chunk.path_hash(),
+                        error
+                    );
+                    continue;
+                }
+            };
+
+            if is_scannable_chunk(&data) {
+                self.scan_chunk_data(&data);
+                scanned_chunk_count += 1;
+            }
+        }
+
+        Ok(scanned_chunk_count)
+    }
+
+    pub fn scan_chunk_data(&mut self, data: &[u8]) {
+        for path in extract_path_candidates(data) {
+            self.try_path(path);
+        }
+    }
+
+    /// Try a path and all of its variants
+    pub fn try_path(&mut self, path: &str) {
+        let path = normalize_path(path);
+        if !self.tried_paths.insert(path.clone()) {
+            return;
+        }
+
+        for variant in generate_path_variants(&path) {
+            let path_hash = hash_chunk_path(&variant);
+            if self.unresolved.remove(&path_hash) {
+                tracing::info!("discovered path (path_hash: {:x}): {}", path_hash, variant);
+                self.discovered.insert(path_hash, variant);
+            }
+        }
+    }
+
+    pub fn unresolved_count(&self) -> usize {
+        self.unresolved.len()
+    }
+
+    pub fn into_discovered(self) -> HashMap<u64, String> {
+        self.discovered
+    }
+}
+
+/// Append discovered paths to a hashtable file, creating it if it doesn't exist
+pub fn append_discovered_paths(
+    hashtable_path: impl AsRef<Path>,
+    discovered: &HashMap<u64, String>,
+) -> eyre::Result<()> {
+    let hashtable_path = hashtable_path.as_ref();
+    if let Some(directory) = hashtable_path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+
+    let mut writer = BufWriter::new(
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(hashtable_path)?,
+    );
+
+    let mut discovered = discovered.iter().collect::<Vec<_>>();
+    discovered.sort_by_key(|(path_hash, _)| **path_hash);
+    for (path_hash, path) in discovered {
+        writeln!(writer, "{:016x} {}", path_hash, path)?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn is_scannable_chunk(data: &[u8]) -> bool {
+    SCANNED_FILE_KINDS.contains(&LeagueFileKind::identify_from_bytes(data)) || is_text(data)
+}
+
+fn is_text(data: &[u8]) -> bool {
+    !data.is_empty()
+        && data[..data.len().min(TEXT_SNIFF_SIZE)]
+            .iter()
+            .all(|x| x.is_ascii_graphic() || x.is_ascii_whitespace())
+}
+
+fn is_path_char(x: u8) -> bool {
+    x.is_ascii_alphanumeric() || matches!(x, b'_' | b'-' | b'.' | b'/' | b'\\' | b'+')
+}
+
+/// Extract runs of path characters which end with a file extension
+fn extract_path_candidates(data: &[u8]) -> impl Iterator<Item = &str> {
+    data.split(|x| !is_path_char(*x)).filter_map(|run| {
+        let run = std::str::from_utf8(run).ok()?;
+        let run = run.trim_matches(|x: char| !x.is_ascii_alphanumeric());
+        if run.len() < MIN_PATH_LENGTH {
+            return None;
+        }
+
+        let (stem, extension) = run.rsplit_once('.')?;
+        let is_extension = !extension.is_empty()
+            && extension.len() <= MAX_EXTENSION_LENGTH
+            && extension.chars().all(|x| x.is_ascii_alphanumeric())
+            && extension.chars().any(|x| x.is_ascii_alphabetic());
+
+        (is_extension && !stem.is_empty()).then_some(run)
+    })
+}
+
+fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+        .trim_start_matches('/')
+        .to_lowercase()
+}
+
+/// Generate the path itself along with root folder, extension, locale and skin number variants
+///
+/// Localized paths keep their extension, combining locales with skin numbers and extensions
+/// would explode the amount of variants for voice over paths.
+fn generate_path_variants(path: &str) -> Vec<String> {
+    let mut root_variants = vec![path.to_string()];
+    if !VARIANT_ROOTS
+        .iter()
+        .any(|root| path.starts_with(&format!("{}/", root)))
+    {
+        root_variants.extend(
+            VARIANT_ROOTS
+                .iter()
+                .map(|root| format!("{}/{}", root, path)),
+        );
+    }
+
+    let mut variants = Vec::new();
+    for root_variant in root_variants {
+        let locale_variants = generate_locale_variants(&root_variant);
+        let is_localized = locale_variants.len() > 1;
+
+        for skin_variant in locale_variants
+            .iter()
+            .flat_map(|variant| generate_skin_variants(variant))
+        {
+            if !is_localized && let Some((stem, _)) = skin_variant.rsplit_once('.') {
+                variants.extend(
+                    VARIANT_EXTENSIONS
+                        .iter()
+                        .map(|extension| format!("{}.{}", stem, extension)),
+                );
+            }
+
+            variants.push(skin_variant);
+        }
+    }
+
+    variants
+}
+
+fn generate_locale_variants(path: &str) -> Vec<String> {
+    let Some(locale) = path
+        .split('/')
+        .find(|component| VARIANT_LOCALES.contains(component))
+    else {
+        return vec![path.to_string()];
+    };
+
+    VARIANT_LOCALES
+        .iter()
+        .map(|variant| path.replace(&format!("/{}/", locale), &format!("/{}/", variant)))
+        .collect()
+}
+
+/// Replace the number of every `skin<number>` in the path with every skin number, paths
+/// usually repeat it, e.g. `skins/skin01/ahri_skin01.bin`
+fn generate_skin_variants(path: &str) -> Vec<String> {
+    let skin_number = path.match_indices("skin").find_map(|(start, _)| {
+        let number_start = start + "skin".len();
+        let number_length = path[number_start..]
+            .chars()
+            .take_while(|x| x.is_ascii_digit())
+            .count();
+
+        (number_length > 0).then(|| &path[number_start..number_start + number_length])
+    });
+    let Some(skin_number) = skin_number else {
+        return vec![path.to_string()];
+    };
+
+    let pattern = format!("skin{}", skin_number);
+
+    // keep zero padding, e.g. skin01
+    (0..=MAX_VARIANT_SKIN)
+        .map(|skin| {
+            path.replace(
+                &pattern,
+                &format!("skin{:0width$}", skin, width = skin_number.len()),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,11 @@
+/// Hash a joint name the way animations refer to joints, ELF hash of the lowercased name
+pub fn hash_joint_name(name: impl AsRef<str>) -> u32 {
+    name.as_ref()
+        .bytes()
+        .map(|x| x.to_ascii_lowercase())
+        .fold(0u32, |hash, x| {
+            let hash = (hash << 4).wrapping_add(x as u32);
+            let high = hash & 0xf000_0000;
+            (hash ^ (high >> 24)) & !high
+        })
+}
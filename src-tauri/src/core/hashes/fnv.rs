@@ -0,0 +1,12 @@
+const FNV1A_32_OFFSET_BASIS: u32 = 0x811c9dc5;
+const FNV1A_32_PRIME: u32 = 0x01000193;
+
+/// Hash a name the way BIN files do, 32-bit FNV-1a of the lowercased name
+pub fn hash_bin_name(name: impl AsRef<str>) -> u32 {
+    name.as_ref()
+        .bytes()
+        .map(|x| x.to_ascii_lowercase())
+        .fold(FNV1A_32_OFFSET_BASIS, |hash, x| {
+            (hash ^ x as u32).wrapping_mul(FNV1A_32_PRIME)
+        })
+}
@@ -0,0 +1,13 @@
+mod cache;
+mod discovery;
+mod elf;
+mod fnv;
+mod parse;
+mod update;
+
+pub use cache::*;
+pub use discovery::*;
+pub use elf::*;
+pub use fnv::*;
+pub use parse::*;
+pub use update::*;
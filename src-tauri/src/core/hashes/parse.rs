@@ -0,0 +1,165 @@
+use color_eyre::eyre;
+use flate2::read::MultiGzDecoder;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Amount of invalid lines which get logged individually per file
+const MAX_LOGGED_INVALID_LINES: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashtableParseStats {
+    pub entry_count: usize,
+    pub invalid_line_count: usize,
+}
+
+/// Open a hashtable file, gzip and zstd compressed files are decompressed transparently
+pub fn open_hashtable_file(path: impl AsRef<Path>) -> eyre::Result<Box<dyn BufRead>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let magic = reader.fill_buf()?;
+
+    Ok(if magic.starts_with(GZIP_MAGIC) {
+        Box::new(BufReader::new(MultiGzDecoder::new(reader)))
+    } else if magic.starts_with(ZSTD_MAGIC) {
+        Box::new(BufReader::new(zstd::Decoder::with_buffer(reader)?))
+    } else {
+        Box::new(reader)
+    })
+}
+
+/// Parse `<hash> <path>` lines, calling `on_entry` for every valid one
+///
+/// Empty lines and comments are ignored and malformed lines are skipped and counted, I/O errors
+/// are returned. Comments start with `#` at the start of a line or with ` #` after an entry.
+pub fn parse_hashtable(
+    mut reader: impl BufRead,
+    mut on_entry: impl FnMut(u64, &str),
+) -> eyre::Result<HashtableParseStats> {
+    let mut stats = HashtableParseStats::default();
+    let mut line = Vec::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+
+        match parse_hashtable_line(&line) {
+            Some(Some((hash, path))) => {
+                on_entry(hash, path);
+                stats.entry_count += 1;
+            }
+            Some(None) => {}
+            None => {
+                if stats.invalid_line_count < MAX_LOGGED_INVALID_LINES {
+                    tracing::warn!(
+                        "skipping invalid hashtable line {}: {:?}",
+                        line_number,
+                        String::from_utf8_lossy(&line)
+                    );
+                }
+                stats.invalid_line_count += 1;
+            }
+        }
+    }
+
+    if stats.invalid_line_count > 0 {
+        tracing::warn!(
+            "skipped {} invalid hashtable lines",
+            stats.invalid_line_count
+        );
+    }
+
+    Ok(stats)
+}
+
+/// Returns `None` for an invalid line and `Some(None)` for an empty or comment line
+fn parse_hashtable_line(line: &[u8]) -> Option<Option<(u64, &str)>> {
+    let line = std::str::from_utf8(line).ok()?;
+    let line = match line.find(" #") {
+        Some(comment_start) => &line[..comment_start],
+        None => line,
+    }
+    .trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return Some(None);
+    }
+
+    let (hash, path) = line.split_once(' ')?;
+    if hash.is_empty() || hash.len() > 16 || path.is_empty() {
+        return None;
+    }
+
+    let hash = u64::from_str_radix(hash, 16).ok()?;
+    Some(Some((hash, path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> (Vec<(u64, String)>, HashtableParseStats) {
+        let mut entries = Vec::new();
+        let stats = parse_hashtable(content.as_bytes(), |hash, path| {
+            entries.push((hash, path.to_string()))
+        })
+        .unwrap();
+
+        (entries, stats)
+    }
+
+    #[test]
+    fn parses_entries() {
+        let (entries, stats) =
+            parse("00000000000000ff some/path.bin\r\n1 path with spaces.txt\n\n");
+
+        assert_eq!(
+            entries,
+            [
+                (0xff, "some/path.bin".to_string()),
+                (0x1, "path with spaces.txt".to_string()),
+            ]
+        );
+        assert_eq!(stats.entry_count, 2);
+        assert_eq!(stats.invalid_line_count, 0);
+    }
+
+    #[test]
+    fn ignores_comment_lines() {
+        let (entries, stats) = parse("# exported hashes\n#00000000000000ff path.bin\n");
+
+        assert!(entries.is_empty());
+        assert_eq!(stats.invalid_line_count, 0);
+    }
+
+    #[test]
+    fn strips_trailing_comments() {
+        let (entries, _) = parse("00000000000000ff some/path.bin # bin Aatrox.wad.client\n");
+
+        assert_eq!(entries, [(0xff, "some/path.bin".to_string())]);
+    }
+
+    #[test]
+    fn skips_unresolved_chunk_lines() {
+        // the format unresolved chunks are exported with, which has no path
+        let (entries, stats) = parse("00000000000000ff # bin Aatrox.wad.client\n");
+
+        assert!(entries.is_empty());
+        assert_eq!(stats.invalid_line_count, 1);
+    }
+
+    #[test]
+    fn skips_invalid_lines() {
+        let (entries, stats) = parse("not_a_hash path.bin\n00000000000000000ff too_long.bin\n1\n");
+
+        assert!(entries.is_empty());
+        assert_eq!(stats.invalid_line_count, 3);
+    }
+}
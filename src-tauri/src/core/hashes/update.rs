@@ -0,0 +1,222 @@
+use color_eyre::eyre::{self, Context};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    path::Path,
+};
+
+use crate::utils::{
+    fs::try_create_dir,
+    http::{self, DownloadStatus},
+};
+
+/// Directory inside of the hashtables directory where files are downloaded to, it's skipped when
+/// loading the hashtables so partial downloads never get loaded
+pub const HASHTABLE_DOWNLOAD_DIR: &str = ".download";
+
+pub const DEFAULT_WAD_HASHTABLES_BASE_URL: &str = "https://raw.communitydragon.org/data/hashes/lol";
+
+/// Hashtables which get downloaded, each one is either a single file or split into
+/// `<name>.0`, `<name>.1`, ... parts
+pub const WAD_HASHTABLE_NAMES: &[&str] = &["hashes.game.txt", "hashes.lcu.txt"];
+
+/// ETags of the downloaded hashtable files, used to skip files which haven't changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashtableManifest {
+    pub base_url: String,
+    pub etags: HashMap<String, String>,
+}
+
+impl HashtableManifest {
+    pub fn load_or_default(path: impl AsRef<Path>) -> Self {
+        File::open(path)
+            .ok()
+            .and_then(|file| serde_json::from_reader(file).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> eyre::Result<()> {
+        serde_json::to_writer_pretty(File::create(path)?, self)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HashtableUpdateResult {
+    pub updated_files: Vec<String>,
+    pub unchanged_files: Vec<String>,
+}
+
+/// Download the wad hashtables from `base_url` into `directory`
+///
+/// Files are requested with the ETag of the previous download so unchanged ones are skipped,
+/// ETags are only reused if the base url is the same as last time.
+pub async fn update_wad_hashtables(
+    base_url: &str,
+    directory: impl AsRef<Path>,
+    manifest_path: impl AsRef<Path>,
+    report_progress: impl Fn(f64, Option<&str>) -> eyre::Result<()>,
+) -> eyre::Result<HashtableUpdateResult> {
+    let directory = directory.as_ref();
+    let manifest_path = manifest_path.as_ref();
+    let base_url = base_url.trim_end_matches('/');
+    tracing::info!("updating wad hashtables (base_url: {})", base_url);
+
+    let mut manifest = HashtableManifest::load_or_default(manifest_path);
+    if manifest.base_url != base_url {
+        manifest = HashtableManifest {
+            base_url: base_url.to_string(),
+            ..Default::default()
+        };
+    }
+
+    let client = reqwest::Client::new();
+    let mut result = HashtableUpdateResult::default();
+
+    for (i, hashtable_name) in WAD_HASHTABLE_NAMES.iter().enumerate() {
+        let progress_start = i as f64 / WAD_HASHTABLE_NAMES.len() as f64;
+        let progress_size = 1.0 / WAD_HASHTABLE_NAMES.len() as f64;
+
+        let mut file_names = Vec::new();
+        for part in std::iter::once(None).chain((0..).map(Some)) {
+            let file_name = match part {
+                Some(part) => format!("{}.{}", hashtable_name, part),
+                None => hashtable_name.to_string(),
+            };
+
+            report_progress(progress_start, Some(&file_name))?;
+
+            let status = download_hashtable_file(
+                &client,
+                base_url,
+                directory,
+                &file_name,
+                manifest.etags.get(&file_name).map(|etag| etag.as_str()),
+                |progress| {
+                    report_progress(progress_start + progress * progress_size, Some(&file_name))
+                },
+            )
+            .await
+            .wrap_err(format!("failed to download hashtable: {}", file_name))?;
+
+            match status {
+                DownloadStatus::Downloaded { etag } => {
+                    match etag {
+                        Some(etag) => manifest.etags.insert(file_name.clone(), etag),
+                        None => manifest.etags.remove(&file_name),
+                    };
+                    result.updated_files.push(file_name.clone());
+                }
+                DownloadStatus::NotModified => result.unchanged_files.push(file_name.clone()),
+                // the whole file is missing, so the hashtable is split into parts
+                DownloadStatus::NotFound if part.is_none() => continue,
+                DownloadStatus::NotFound => break,
+            }
+
+            file_names.push(file_name);
+
+            // the hashtable isn't split
+            if part.is_none() {
+                break;
+            }
+        }
+
+        if file_names.is_empty() {
+            tracing::warn!("hashtable not found on the server: {}", hashtable_name);
+            continue;
+        }
+
+        remove_stale_hashtable_files(directory, hashtable_name, &file_names, &mut manifest)?;
+    }
+
+    manifest.save(manifest_path)?;
+    report_progress(1.0, None)?;
+
+    tracing::info!(
+        "wad hashtables updated (updated: {:?}, unchanged: {:?})",
+        result.updated_files,
+        result.unchanged_files
+    );
+
+    Ok(result)
+}
+
+/// Downloads into [`HASHTABLE_DOWNLOAD_DIR`] first so an interrupted download doesn't replace a
+/// valid hashtable
+async fn download_hashtable_file(
+    client: &reqwest::Client,
+    base_url: &str,
+    directory: &Path,
+    file_name: &str,
+    etag: Option<&str>,
+    report_progress: impl Fn(f64) -> eyre::Result<()>,
+) -> eyre::Result<DownloadStatus> {
+    let path = directory.join(file_name);
+    let download_dir = directory.join(HASHTABLE_DOWNLOAD_DIR);
+    try_create_dir(&download_dir)?;
+    let temporary_path = download_dir.join(file_name);
+
+    // the old file is gone, so it has to be downloaded again regardless of the etag
+    let etag = etag.filter(|_| path.is_file());
+
+    let mut progress_result = Ok(());
+    let status = http::download_file(
+        client,
+        format!("{}/{}", base_url, file_name),
+        &temporary_path,
+        etag,
+        |downloaded_bytes, total_size| {
+            if let Some(total_size) = total_size.filter(|total_size| *total_size > 0)
+                && progress_result.is_ok()
+            {
+                progress_result = report_progress(downloaded_bytes as f64 / total_size as f64);
+            }
+        },
+    )
+    .await;
+
+    let status = match status.and_then(|status| progress_result.map(|_| status)) {
+        Ok(status) => status,
+        Err(error) => {
+            let _ = fs::remove_file(&temporary_path);
+            return Err(error);
+        }
+    };
+
+    if let DownloadStatus::Downloaded { .. } = status {
+        fs::rename(&temporary_path, &path)?;
+    }
+
+    Ok(status)
+}
+
+/// Remove local files of a hashtable which aren't on the server anymore, e.g. parts of a
+/// hashtable which isn't split anymore
+fn remove_stale_hashtable_files(
+    directory: &Path,
+    hashtable_name: &str,
+    file_names: &[String],
+    manifest: &mut HashtableManifest,
+) -> eyre::Result<()> {
+    for entry in fs::read_dir(directory)?.filter_map(|x| x.ok()) {
+        let Ok(file_name) = entry.file_name().into_string() else {
+            continue;
+        };
+
+        let is_hashtable_file = file_name == hashtable_name
+            || file_name
+                .strip_prefix(hashtable_name)
+                .and_then(|suffix| suffix.strip_prefix('.'))
+                .is_some_and(|part| part.chars().all(|x| x.is_ascii_digit()));
+
+        if is_hashtable_file && !file_names.contains(&file_name) {
+            tracing::info!("removing stale hashtable file: {}", file_name);
+            fs::remove_file(entry.path())?;
+            manifest.etags.remove(&file_name);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,709 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use color_eyre::eyre::{self, Context, ContextCompat, eyre};
+use std::io::Read;
+
+const MAP_GEOMETRY_MAGIC: &[u8; 4] = b"OEGM";
+
+/// Versions of the environment asset layout which can be read
+///
+/// Version 16 is left out on purpose, there are no known maps with it to verify its layout
+/// against, so it's rejected instead of guessing between the layouts of 15 and 17.
+pub const SUPPORTED_MAP_GEOMETRY_VERSIONS: &[u32] = &[11, 12, 13, 14, 15, 17];
+
+/// Vertex declarations always have room for this many elements, unused ones are padding
+const MAX_VERTEX_ELEMENTS: usize = 15;
+
+/// Meshes of a map geometry (mapgeo) file
+///
+/// Only the geometry is read, the scene graph and planar reflectors which follow the meshes are
+/// skipped.
+#[derive(Debug, Clone)]
+pub struct MapGeometry {
+    pub version: u32,
+    pub meshes: Vec<MapGeometryMesh>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapGeometryMesh {
+    /// Stored in the file up to version 11, later meshes are named after their index
+    pub name: String,
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Option<Vec<[f32; 3]>>,
+    pub uvs: Option<Vec<[f32; 2]>>,
+    pub indices: Vec<u16>,
+    pub submeshes: Vec<MapGeometrySubmesh>,
+    /// Column major
+    pub transform: [f32; 16],
+    pub bounding_box: ([f32; 3], [f32; 3]),
+    /// Bit flags of the layers (e.g. dragon variants) the mesh is visible on
+    pub visibility_layers: u8,
+    pub quality_filter: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct MapGeometrySubmesh {
+    pub material: String,
+    pub start_index: u32,
+    pub index_count: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VertexElement {
+    name: u32,
+    format: u32,
+}
+
+#[derive(Debug, Clone)]
+struct VertexDeclaration {
+    elements: Vec<VertexElement>,
+}
+
+const VERTEX_ELEMENT_POSITION: u32 = 0;
+const VERTEX_ELEMENT_NORMAL: u32 = 2;
+const VERTEX_ELEMENT_TEXCOORD0: u32 = 7;
+
+const VERTEX_FORMAT_XY_FLOAT32: u32 = 1;
+const VERTEX_FORMAT_XYZ_FLOAT32: u32 = 2;
+
+impl MapGeometry {
+    pub fn read<TSource: Read>(reader: &mut TSource) -> eyre::Result<Self> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAP_GEOMETRY_MAGIC {
+            return Err(eyre!("invalid map geometry magic: {:?}", magic));
+        }
+
+        let version = reader.read_u32::<LittleEndian>()?;
+        if !SUPPORTED_MAP_GEOMETRY_VERSIONS.contains(&version) {
+            return Err(eyre!("unsupported map geometry version: {}", version));
+        }
+
+        // shader texture overrides, e.g. the baked paint of the whole map
+        match version {
+            17.. => {
+                for _ in 0..reader.read_u32::<LittleEndian>()? {
+                    reader.read_u32::<LittleEndian>()?;
+                    read_sized_string(reader)?;
+                }
+            }
+            _ => {
+                read_sized_string(reader)?;
+                read_sized_string(reader)?;
+            }
+        }
+
+        let declaration_count = reader.read_u32::<LittleEndian>()?;
+        let declarations = (0..declaration_count)
+            .map(|_| VertexDeclaration::read(reader))
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        let vertex_buffer_count = reader.read_u32::<LittleEndian>()?;
+        let mut vertex_buffers = Vec::new();
+        for _ in 0..vertex_buffer_count {
+            if version >= 13 {
+                reader.read_u8()?;
+            }
+            vertex_buffers.push(read_buffer(reader)?);
+        }
+
+        let index_buffer_count = reader.read_u32::<LittleEndian>()?;
+        let mut index_buffers = Vec::new();
+        for _ in 0..index_buffer_count {
+            if version >= 13 {
+                reader.read_u8()?;
+            }
+            let buffer = read_buffer(reader)?;
+            index_buffers.push(
+                buffer
+                    .chunks_exact(2)
+                    .map(|index| u16::from_le_bytes([index[0], index[1]]))
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        let mesh_count = reader.read_u32::<LittleEndian>()?;
+        let mut meshes = Vec::new();
+        for index in 0..mesh_count as usize {
+            let buffers = MapGeometryBuffers {
+                declarations: &declarations,
+                vertex_buffers: &vertex_buffers,
+                index_buffers: &index_buffers,
+            };
+            meshes.push(
+                MapGeometryMesh::read(reader, version, index, &buffers)
+                    .wrap_err(format!("failed to read mesh {}", index))?,
+            );
+        }
+
+        Ok(Self { version, meshes })
+    }
+}
+
+/// Buffers shared by the meshes, which refer to them by index
+struct MapGeometryBuffers<'a> {
+    declarations: &'a [VertexDeclaration],
+    vertex_buffers: &'a [Box<[u8]>],
+    index_buffers: &'a [Vec<u16>],
+}
+
+impl MapGeometryMesh {
+    fn read<TSource: Read>(
+        reader: &mut TSource,
+        version: u32,
+        index: usize,
+        buffers: &MapGeometryBuffers,
+    ) -> eyre::Result<Self> {
+        let name = match version {
+            ..=11 => read_sized_string(reader)?,
+            _ => format!("MapGeo_Instance_{}", index),
+        };
+
+        let vertex_count = reader.read_u32::<LittleEndian>()? as usize;
+        let declaration_count = reader.read_u32::<LittleEndian>()? as usize;
+        let base_declaration = reader.read_u32::<LittleEndian>()? as usize;
+        let vertex_buffer_ids = (0..declaration_count)
+            .map(|_| reader.read_u32::<LittleEndian>().map(|id| id as usize))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let index_count = reader.read_u32::<LittleEndian>()? as usize;
+        let index_buffer_id = reader.read_u32::<LittleEndian>()? as usize;
+
+        let mut visibility_layers = 0xff;
+        if version >= 13 {
+            visibility_layers = reader.read_u8()?;
+        }
+        if version >= 15 {
+            // path hash of the visibility controller
+            reader.read_u32::<LittleEndian>()?;
+        }
+
+        let submesh_count = reader.read_u32::<LittleEndian>()?;
+        let mut submeshes = Vec::new();
+        for _ in 0..submesh_count {
+            // hash of the material name
+            reader.read_u32::<LittleEndian>()?;
+            let material = read_sized_string(reader)?;
+            let start_index = reader.read_u32::<LittleEndian>()?;
+            let index_count = reader.read_u32::<LittleEndian>()?;
+            // vertex range
+            reader.read_u32::<LittleEndian>()?;
+            reader.read_u32::<LittleEndian>()?;
+
+            submeshes.push(MapGeometrySubmesh {
+                material,
+                start_index,
+                index_count,
+            });
+        }
+
+        // disable backface culling
+        reader.read_u8()?;
+
+        let bounding_box = (read_vec3(reader)?, read_vec3(reader)?);
+        let mut transform = [0.0; 16];
+        reader.read_f32_into::<LittleEndian>(&mut transform)?;
+
+        let quality_filter = reader.read_u8()?;
+        if version <= 12 {
+            visibility_layers = reader.read_u8()?;
+        }
+
+        // render flags, widened to 16 bits in version 14
+        if version <= 13 {
+            reader.read_u8()?;
+        } else {
+            reader.read_u16::<LittleEndian>()?;
+        }
+
+        // baked light and stationary light samplers, followed by the baked paint sampler which
+        // became per mesh texture overrides in version 17
+        read_sampler(reader)?;
+        read_sampler(reader)?;
+        match version {
+            ..=11 => {}
+            12..=16 => read_sampler(reader)?,
+            _ => {
+                for _ in 0..reader.read_u32::<LittleEndian>()? {
+                    reader.read_u32::<LittleEndian>()?;
+                    read_sized_string(reader)?;
+                }
+            }
+        }
+
+        let indices = buffers
+            .index_buffers
+            .get(index_buffer_id)
+            .wrap_err(format!("invalid index buffer: {}", index_buffer_id))?
+            .get(..index_count)
+            .wrap_err("index buffer is too small")?
+            .to_vec();
+        for submesh in &submeshes {
+            let end = submesh.start_index as usize + submesh.index_count as usize;
+            if end > indices.len() {
+                return Err(eyre!("invalid submesh index range: {}", submesh.material));
+            }
+        }
+
+        let mut positions = None;
+        let mut normals = None;
+        let mut uvs = None;
+        for (i, buffer_id) in vertex_buffer_ids.into_iter().enumerate() {
+            let declaration = buffers
+                .declarations
+                .get(base_declaration + i)
+                .wrap_err(format!(
+                    "invalid vertex declaration: {}",
+                    base_declaration + i
+                ))?;
+            let buffer = buffers
+                .vertex_buffers
+                .get(buffer_id)
+                .wrap_err(format!("invalid vertex buffer: {}", buffer_id))?;
+
+            let stride = declaration.stride();
+            if buffer.len() < stride * vertex_count {
+                return Err(eyre!("vertex buffer is too small: {}", buffer_id));
+            }
+
+            let mut offset = 0;
+            for element in &declaration.elements {
+                match (element.name, element.format) {
+                    (VERTEX_ELEMENT_POSITION, VERTEX_FORMAT_XYZ_FLOAT32) => {
+                        positions = Some(read_vertices(buffer, stride, offset, vertex_count));
+                    }
+                    (VERTEX_ELEMENT_NORMAL, VERTEX_FORMAT_XYZ_FLOAT32) => {
+                        normals = Some(read_vertices(buffer, stride, offset, vertex_count));
+                    }
+                    (VERTEX_ELEMENT_TEXCOORD0, VERTEX_FORMAT_XY_FLOAT32) => {
+                        uvs = Some(read_vertices(buffer, stride, offset, vertex_count));
+                    }
+                    _ => {}
+                }
+                offset += element.size();
+            }
+        }
+
+        Ok(Self {
+            name,
+            positions: positions.wrap_err("mesh has no positions")?,
+            normals,
+            uvs,
+            indices,
+            submeshes,
+            transform,
+            bounding_box,
+            visibility_layers,
+            quality_filter,
+        })
+    }
+}
+
+impl VertexDeclaration {
+    fn read<TSource: Read>(reader: &mut TSource) -> eyre::Result<Self> {
+        // usage (static, dynamic, stream)
+        reader.read_u32::<LittleEndian>()?;
+
+        let element_count = reader.read_u32::<LittleEndian>()? as usize;
+        if element_count > MAX_VERTEX_ELEMENTS {
+            return Err(eyre!("invalid vertex element count: {}", element_count));
+        }
+
+        let mut elements = Vec::with_capacity(element_count);
+        for i in 0..MAX_VERTEX_ELEMENTS {
+            let element = VertexElement {
+                name: reader.read_u32::<LittleEndian>()?,
+                format: reader.read_u32::<LittleEndian>()?,
+            };
+            if i < element_count {
+                elements.push(element);
+            }
+        }
+
+        Ok(Self { elements })
+    }
+
+    fn stride(&self) -> usize {
+        self.elements.iter().map(VertexElement::size).sum()
+    }
+}
+
+impl VertexElement {
+    fn size(&self) -> usize {
+        match self.format {
+            0 => 4,
+            1 => 8,
+            2 => 12,
+            3 => 16,
+            // packed colors
+            _ => 4,
+        }
+    }
+}
+
+fn read_vertices<const N: usize>(
+    buffer: &[u8],
+    stride: usize,
+    offset: usize,
+    vertex_count: usize,
+) -> Vec<[f32; N]> {
+    (0..vertex_count)
+        .map(|vertex| {
+            let start = vertex * stride + offset;
+            std::array::from_fn(|i| {
+                let component = start + i * 4;
+                f32::from_le_bytes(buffer[component..component + 4].try_into().unwrap())
+            })
+        })
+        .collect()
+}
+
+fn read_buffer<TSource: Read>(reader: &mut TSource) -> eyre::Result<Box<[u8]>> {
+    let size = reader.read_u32::<LittleEndian>()?;
+    Ok(read_bytes(reader, size)?.into())
+}
+
+fn read_sized_string<TSource: Read>(reader: &mut TSource) -> eyre::Result<String> {
+    let length = reader.read_u32::<LittleEndian>()?;
+    let bytes = read_bytes(reader, length)?;
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Sizes come from the file, so the buffer grows with the data which is actually there instead
+/// of being allocated up front
+fn read_bytes<TSource: Read>(reader: &mut TSource, size: u32) -> eyre::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.by_ref().take(size as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != size as usize {
+        return Err(eyre!(
+            "unexpected end of data (expected: {}, actual: {})",
+            size,
+            bytes.len()
+        ));
+    }
+
+    Ok(bytes)
+}
+
+fn read_vec3<TSource: Read>(reader: &mut TSource) -> eyre::Result<[f32; 3]> {
+    let mut vec = [0.0; 3];
+    reader.read_f32_into::<LittleEndian>(&mut vec)?;
+
+    Ok(vec)
+}
+
+/// Texture along with the scale and bias of the UVs used to sample it
+fn read_sampler<TSource: Read>(reader: &mut TSource) -> eyre::Result<()> {
+    read_sized_string(reader)?;
+    let mut scale_bias = [0.0; 4];
+    reader.read_f32_into::<LittleEndian>(&mut scale_bias)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use byteorder::WriteBytesExt;
+    use std::io::{Cursor, Write};
+
+    const POSITIONS: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    const UVS: [[f32; 2]; 3] = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+    const INDICES: [u16; 3] = [0, 1, 2];
+
+    /// Writes map geometry with a single triangle mesh, the fields allow breaking it
+    struct TestMapGeometry {
+        version: u32,
+        vertex_buffer_id: u32,
+        index_buffer_id: u32,
+        index_count: u32,
+        base_declaration: u32,
+        submesh_start_index: u32,
+    }
+
+    impl TestMapGeometry {
+        fn new(version: u32) -> Self {
+            Self {
+                version,
+                vertex_buffer_id: 0,
+                index_buffer_id: 0,
+                index_count: INDICES.len() as u32,
+                base_declaration: 0,
+                submesh_start_index: 0,
+            }
+        }
+
+        fn write(&self) -> Vec<u8> {
+            let mut writer = Cursor::new(Vec::new());
+            self.write_to(&mut writer).unwrap();
+
+            writer.into_inner()
+        }
+
+        fn write_to(&self, writer: &mut impl Write) -> std::io::Result<()> {
+            let version = self.version;
+            writer.write_all(MAP_GEOMETRY_MAGIC)?;
+            writer.write_u32::<LittleEndian>(version)?;
+
+            match version {
+                17.. => {
+                    writer.write_u32::<LittleEndian>(1)?;
+                    writer.write_u32::<LittleEndian>(0)?;
+                    write_sized_string(writer, "ASSETS/Maps/Paint.dds")?;
+                }
+                _ => {
+                    write_sized_string(writer, "")?;
+                    write_sized_string(writer, "")?;
+                }
+            }
+
+            // a single declaration with positions and uvs
+            writer.write_u32::<LittleEndian>(1)?;
+            writer.write_u32::<LittleEndian>(0)?;
+            writer.write_u32::<LittleEndian>(2)?;
+            for i in 0..MAX_VERTEX_ELEMENTS {
+                let (name, format) = match i {
+                    0 => (VERTEX_ELEMENT_POSITION, VERTEX_FORMAT_XYZ_FLOAT32),
+                    1 => (VERTEX_ELEMENT_TEXCOORD0, VERTEX_FORMAT_XY_FLOAT32),
+                    _ => (0, 0),
+                };
+                writer.write_u32::<LittleEndian>(name)?;
+                writer.write_u32::<LittleEndian>(format)?;
+            }
+
+            let mut vertices = Vec::new();
+            for (position, uv) in POSITIONS.iter().zip(&UVS) {
+                for component in position.iter().chain(uv) {
+                    vertices.write_f32::<LittleEndian>(*component)?;
+                }
+            }
+            writer.write_u32::<LittleEndian>(1)?;
+            if version >= 13 {
+                writer.write_u8(0)?;
+            }
+            write_buffer(writer, &vertices)?;
+
+            let mut indices = Vec::new();
+            for index in INDICES {
+                indices.write_u16::<LittleEndian>(index)?;
+            }
+            writer.write_u32::<LittleEndian>(1)?;
+            if version >= 13 {
+                writer.write_u8(0)?;
+            }
+            write_buffer(writer, &indices)?;
+
+            writer.write_u32::<LittleEndian>(1)?;
+            self.write_mesh(writer)
+        }
+
+        fn write_mesh(&self, writer: &mut impl Write) -> std::io::Result<()> {
+            let version = self.version;
+            if version <= 11 {
+                write_sized_string(writer, "Mesh")?;
+            }
+
+            writer.write_u32::<LittleEndian>(POSITIONS.len() as u32)?;
+            writer.write_u32::<LittleEndian>(1)?;
+            writer.write_u32::<LittleEndian>(self.base_declaration)?;
+            writer.write_u32::<LittleEndian>(self.vertex_buffer_id)?;
+            writer.write_u32::<LittleEndian>(self.index_count)?;
+            writer.write_u32::<LittleEndian>(self.index_buffer_id)?;
+
+            if version >= 13 {
+                writer.write_u8(0b11)?;
+            }
+            if version >= 15 {
+                writer.write_u32::<LittleEndian>(0)?;
+            }
+
+            writer.write_u32::<LittleEndian>(1)?;
+            writer.write_u32::<LittleEndian>(0)?;
+            write_sized_string(writer, "Maps/Material")?;
+            writer.write_u32::<LittleEndian>(self.submesh_start_index)?;
+            writer.write_u32::<LittleEndian>(INDICES.len() as u32)?;
+            writer.write_u32::<LittleEndian>(0)?;
+            writer.write_u32::<LittleEndian>(POSITIONS.len() as u32 - 1)?;
+
+            writer.write_u8(0)?;
+            for component in [0.0, 0.0, 0.0, 1.0, 1.0, 0.0] {
+                writer.write_f32::<LittleEndian>(component)?;
+            }
+            for i in 0..16 {
+                let component = if i % 5 == 0 { 1.0 } else { 0.0 };
+                writer.write_f32::<LittleEndian>(component)?;
+            }
+
+            writer.write_u8(0x1f)?;
+            if version <= 12 {
+                writer.write_u8(0b11)?;
+            }
+            if version <= 13 {
+                writer.write_u8(0)?;
+            } else {
+                writer.write_u16::<LittleEndian>(0)?;
+            }
+
+            write_sampler(writer)?;
+            write_sampler(writer)?;
+            match version {
+                ..=11 => {}
+                12..=16 => write_sampler(writer)?,
+                _ => writer.write_u32::<LittleEndian>(0)?,
+            }
+
+            Ok(())
+        }
+    }
+
+    fn write_buffer(writer: &mut impl Write, buffer: &[u8]) -> std::io::Result<()> {
+        writer.write_u32::<LittleEndian>(buffer.len() as u32)?;
+        writer.write_all(buffer)
+    }
+
+    fn write_sized_string(writer: &mut impl Write, string: &str) -> std::io::Result<()> {
+        write_buffer(writer, string.as_bytes())
+    }
+
+    fn write_sampler(writer: &mut impl Write) -> std::io::Result<()> {
+        write_sized_string(writer, "")?;
+        for component in [1.0, 1.0, 0.0, 0.0] {
+            writer.write_f32::<LittleEndian>(component)?;
+        }
+
+        Ok(())
+    }
+
+    fn read(data: Vec<u8>) -> eyre::Result<MapGeometry> {
+        MapGeometry::read(&mut Cursor::new(data))
+    }
+
+    #[test]
+    fn reads_supported_versions() {
+        for &version in SUPPORTED_MAP_GEOMETRY_VERSIONS {
+            let geometry = read(TestMapGeometry::new(version).write())
+                .unwrap_or_else(|error| panic!("version {}: {:?}", version, error));
+
+            assert_eq!(geometry.version, version);
+            assert_eq!(geometry.meshes.len(), 1, "version {}", version);
+
+            let mesh = &geometry.meshes[0];
+            let name = match version {
+                ..=11 => "Mesh",
+                _ => "MapGeo_Instance_0",
+            };
+            assert_eq!(mesh.name, name, "version {}", version);
+            assert_eq!(mesh.positions, POSITIONS, "version {}", version);
+            assert_eq!(
+                mesh.uvs.as_deref(),
+                Some(UVS.as_slice()),
+                "version {}",
+                version
+            );
+            assert!(mesh.normals.is_none(), "version {}", version);
+            assert_eq!(mesh.indices, INDICES, "version {}", version);
+            assert_eq!(mesh.submeshes.len(), 1, "version {}", version);
+            assert_eq!(mesh.submeshes[0].material, "Maps/Material");
+            assert_eq!(mesh.submeshes[0].index_count, 3);
+            assert_eq!(mesh.bounding_box, ([0.0; 3], [1.0, 1.0, 0.0]));
+            assert_eq!(mesh.transform[0], 1.0);
+            assert_eq!(mesh.transform[15], 1.0);
+            assert_eq!(mesh.visibility_layers, 0b11, "version {}", version);
+            assert_eq!(mesh.quality_filter, 0x1f, "version {}", version);
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        for version in [10, 16, 18] {
+            assert!(
+                read(TestMapGeometry::new(version).write()).is_err(),
+                "version {}",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_magic() {
+        let mut data = TestMapGeometry::new(17).write();
+        data[0] = b'X';
+
+        assert!(read(data).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        for &version in SUPPORTED_MAP_GEOMETRY_VERSIONS {
+            let data = TestMapGeometry::new(version).write();
+            for length in 0..data.len() {
+                assert!(
+                    read(data[..length].to_vec()).is_err(),
+                    "version {}, length {}",
+                    version,
+                    length
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_buffers() {
+        // the size of the first vertex buffer, which follows the 8 byte header, the shader texture
+        // override and the vertex declaration
+        let mut data = TestMapGeometry::new(17).write();
+        let offset =
+            8 + 4 + 4 + 4 + "ASSETS/Maps/Paint.dds".len() + 4 + 8 + MAX_VERTEX_ELEMENTS * 8 + 4 + 1;
+        assert_eq!(
+            u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()),
+            (POSITIONS.len() * 20) as u32
+        );
+        data[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        assert!(read(data).is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_buffer_ids() {
+        for &version in SUPPORTED_MAP_GEOMETRY_VERSIONS {
+            let geometries = [
+                TestMapGeometry {
+                    vertex_buffer_id: 1,
+                    ..TestMapGeometry::new(version)
+                },
+                TestMapGeometry {
+                    index_buffer_id: 1,
+                    ..TestMapGeometry::new(version)
+                },
+                TestMapGeometry {
+                    base_declaration: 1,
+                    ..TestMapGeometry::new(version)
+                },
+            ];
+
+            for geometry in geometries {
+                assert!(read(geometry.write()).is_err(), "version {}", version);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_indices() {
+        for &version in SUPPORTED_MAP_GEOMETRY_VERSIONS {
+            let geometries = [
+                TestMapGeometry {
+                    index_count: INDICES.len() as u32 + 1,
+                    ..TestMapGeometry::new(version)
+                },
+                TestMapGeometry {
+                    submesh_start_index: 1,
+                    ..TestMapGeometry::new(version)
+                },
+            ];
+
+            for geometry in geometries {
+                assert!(read(geometry.write()).is_err(), "version {}", version);
+            }
+        }
+    }
+}
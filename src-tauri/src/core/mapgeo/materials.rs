@@ -0,0 +1,73 @@
+use league_toolkit::meta::{BinTree, PropertyValueEnum, value::StructValue};
+
+use crate::core::hashes::hash_bin_name;
+
+/// Sampler names which hold the diffuse texture of a material
+const DIFFUSE_SAMPLER_NAMES: &[&str] = &["DiffuseTexture", "Diffuse_Texture", "BaseTexture"];
+
+/// The materials bin which belongs to a map geometry file, e.g. `base_srx.mapgeo` and
+/// `base_srx.materials.bin`
+pub fn map_materials_path(map_geometry_path: &str) -> String {
+    let stem = map_geometry_path
+        .strip_suffix(".mapgeo")
+        .unwrap_or(map_geometry_path);
+
+    format!("{}.materials.bin", stem)
+}
+
+/// Find the path of the diffuse texture of a material, which is defined in the materials bin of
+/// the map as an object at the path of the material name
+pub fn find_diffuse_texture<'a>(materials: &'a BinTree, material: &str) -> Option<&'a str> {
+    let object = materials.objects.get(&hash_bin_name(material))?;
+
+    object
+        .properties
+        .values()
+        .find_map(|property| find_diffuse_texture_in_value(&property.value))
+}
+
+fn find_diffuse_texture_in_value(value: &PropertyValueEnum) -> Option<&str> {
+    match value {
+        PropertyValueEnum::Container(container) => container
+            .items
+            .iter()
+            .find_map(find_diffuse_texture_in_value),
+        PropertyValueEnum::UnorderedContainer(container) => container
+            .0
+            .items
+            .iter()
+            .find_map(find_diffuse_texture_in_value),
+        PropertyValueEnum::Struct(value) => find_diffuse_texture_in_sampler(value),
+        PropertyValueEnum::Embedded(value) => find_diffuse_texture_in_sampler(&value.0),
+        _ => None,
+    }
+}
+
+/// Samplers are structs with the name of the sampler and the path of its texture as strings
+fn find_diffuse_texture_in_sampler(sampler: &StructValue) -> Option<&str> {
+    let strings = sampler
+        .properties
+        .values()
+        .filter_map(|property| match &property.value {
+            PropertyValueEnum::String(value) => Some(value.0.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let is_diffuse = strings.iter().any(|value| {
+        DIFFUSE_SAMPLER_NAMES
+            .iter()
+            .any(|name| value.eq_ignore_ascii_case(name))
+    });
+    if !is_diffuse {
+        return sampler
+            .properties
+            .values()
+            .find_map(|property| find_diffuse_texture_in_value(&property.value));
+    }
+
+    strings.into_iter().find(|value| {
+        let value = value.to_lowercase();
+        value.ends_with(".tex") || value.ends_with(".dds")
+    })
+}
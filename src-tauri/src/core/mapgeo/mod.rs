@@ -0,0 +1,5 @@
+mod asset;
+mod materials;
+
+pub use asset::*;
+pub use materials::*;
@@ -1 +1,6 @@
+pub mod bin;
+pub mod gltf;
+pub mod hashes;
+pub mod mapgeo;
+pub mod text;
 pub mod wad;
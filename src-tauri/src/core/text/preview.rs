@@ -0,0 +1,195 @@
+use serde::Serialize;
+use std::path::Path;
+
+/// Size of the data after which text previews get truncated
+pub const TEXT_PREVIEW_MAX_SIZE: usize = 4 * 1024 * 1024;
+
+/// Amount of bytes at the start of a chunk which are looked at to decide whether it's text
+const TEXT_DETECTION_SAMPLE_SIZE: usize = 8 * 1024;
+
+/// Share of control characters a sample may contain and still be considered text
+const MAX_CONTROL_CHARACTER_RATIO: f64 = 0.05;
+
+const UTF8_BOM: &[u8] = &[0xef, 0xbb, 0xbf];
+const UTF16_LE_BOM: &[u8] = &[0xff, 0xfe];
+const UTF16_BE_BOM: &[u8] = &[0xfe, 0xff];
+
+/// Language hint for syntax highlighting, serialized as the usual highlighter ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextLanguage {
+    PlainText,
+    Lua,
+    Json,
+    Ini,
+    Xml,
+    Html,
+    Css,
+    JavaScript,
+    Glsl,
+    Hlsl,
+    Csv,
+    Yaml,
+    Python,
+}
+
+impl TextLanguage {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Some(match extension.to_ascii_lowercase().as_str() {
+            "txt" | "log" | "md" => Self::PlainText,
+            "lua" => Self::Lua,
+            "json" => Self::Json,
+            "ini" | "cfg" => Self::Ini,
+            "xml" | "svg" => Self::Xml,
+            "html" | "htm" => Self::Html,
+            "css" => Self::Css,
+            "js" => Self::JavaScript,
+            "glsl" | "vert" | "frag" | "vs" | "ps" => Self::Glsl,
+            "hlsl" | "fx" | "fxh" | "vs_2_0" | "ps_2_0" | "vs_3_0" | "ps_3_0" => Self::Hlsl,
+            "csv" => Self::Csv,
+            "yaml" | "yml" => Self::Yaml,
+            "py" => Self::Python,
+            _ => return None,
+        })
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        Self::from_extension(path.as_ref().extension()?.to_str()?)
+    }
+
+    /// Guess the language from the start of the content, for chunks without a known extension
+    pub fn from_content(content: &str) -> Self {
+        let content = content.trim_start();
+
+        if content.starts_with("<?xml") {
+            Self::Xml
+        } else if content.starts_with("<!DOCTYPE html") || content.starts_with("<html") {
+            Self::Html
+        } else if content.starts_with('{') || content.starts_with('[') {
+            Self::Json
+        } else if content.starts_with("#version") {
+            Self::Glsl
+        } else {
+            Self::PlainText
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// Fallback for anything which isn't valid UTF-8
+    Latin1,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextPreview {
+    pub content: String,
+    pub language: TextLanguage,
+    pub encoding: TextEncoding,
+    /// Whether the content was cut off at the size limit
+    pub is_truncated: bool,
+    /// Size of the undecoded data
+    pub total_size: usize,
+}
+
+/// Whether a chunk looks like text, either by the extension of its path or by its data
+pub fn is_text_chunk(path: impl AsRef<Path>, data: &[u8]) -> bool {
+    TextLanguage::from_path(path).is_some() || is_text_data(data)
+}
+
+/// Byte heuristic, data with a BOM is text, otherwise the start of it must not contain any NUL
+/// bytes and only few control characters
+pub fn is_text_data(data: &[u8]) -> bool {
+    if data.starts_with(UTF8_BOM)
+        || data.starts_with(UTF16_LE_BOM)
+        || data.starts_with(UTF16_BE_BOM)
+    {
+        return true;
+    }
+
+    let sample = &data[..data.len().min(TEXT_DETECTION_SAMPLE_SIZE)];
+    if sample.is_empty() || sample.contains(&0) {
+        return false;
+    }
+
+    let control_character_count = sample
+        .iter()
+        .filter(|&&byte| byte < 0x20 && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b))
+        .count();
+
+    (control_character_count as f64 / sample.len() as f64) <= MAX_CONTROL_CHARACTER_RATIO
+}
+
+/// Decode text data, truncating it at `max_size` bytes
+pub fn read_text_preview(path: impl AsRef<Path>, data: &[u8], max_size: usize) -> TextPreview {
+    let (content, encoding, is_truncated) = decode_text(data, max_size);
+    let language =
+        TextLanguage::from_path(path).unwrap_or_else(|| TextLanguage::from_content(&content));
+
+    TextPreview {
+        content,
+        language,
+        encoding,
+        is_truncated,
+        total_size: data.len(),
+    }
+}
+
+/// Decode UTF-8 and UTF-16 (with a BOM), falling back to Latin-1 for anything else
+fn decode_text(data: &[u8], max_size: usize) -> (String, TextEncoding, bool) {
+    if let Some(data) = data.strip_prefix(UTF16_LE_BOM) {
+        let (data, is_truncated) = truncate(data, max_size & !1);
+        return (
+            decode_utf16(data, u16::from_le_bytes),
+            TextEncoding::Utf16Le,
+            is_truncated,
+        );
+    }
+    if let Some(data) = data.strip_prefix(UTF16_BE_BOM) {
+        let (data, is_truncated) = truncate(data, max_size & !1);
+        return (
+            decode_utf16(data, u16::from_be_bytes),
+            TextEncoding::Utf16Be,
+            is_truncated,
+        );
+    }
+
+    let data = data.strip_prefix(UTF8_BOM).unwrap_or(data);
+    let (data, is_truncated) = truncate(data, max_size);
+
+    match std::str::from_utf8(data) {
+        Ok(content) => (content.to_string(), TextEncoding::Utf8, is_truncated),
+        // truncating can split the last character
+        Err(error) if is_truncated && error.error_len().is_none() => (
+            String::from_utf8_lossy(&data[..error.valid_up_to()]).into_owned(),
+            TextEncoding::Utf8,
+            is_truncated,
+        ),
+        Err(_) => (
+            data.iter().map(|&byte| byte as char).collect(),
+            TextEncoding::Latin1,
+            is_truncated,
+        ),
+    }
+}
+
+fn decode_utf16(data: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    char::decode_utf16(
+        data.chunks_exact(2)
+            .map(|bytes| from_bytes([bytes[0], bytes[1]])),
+    )
+    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+    .collect()
+}
+
+fn truncate(data: &[u8], max_size: usize) -> (&[u8], bool) {
+    match data.len() > max_size {
+        true => (&data[..max_size], true),
+        false => (data, false),
+    }
+}
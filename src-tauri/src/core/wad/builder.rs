@@ -0,0 +1,532 @@
+use byteorder::{LittleEndian, WriteBytesExt};
+use camino::Utf8Path;
+use color_eyre::eyre::{self, Context, eyre};
+use flate2::{Compression, write::GzEncoder};
+use league_toolkit::wad::WadChunkCompression;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Seek, SeekFrom, Write},
+    path::Path,
+};
+use walkdir::WalkDir;
+use xxhash_rust::{xxh3::xxh3_64, xxh64::xxh64};
+
+use super::{WadSubchunkToc, WadSubchunkTocEntry, compress_subchunks};
+
+const WAD_MAGIC: &[u8; 2] = b"RW";
+const WAD_VERSION_MAJOR: u8 = 3;
+const WAD_VERSION_MINOR: u8 = 4;
+
+const WAD_SIGNATURE_SIZE: usize = 256;
+const WAD_HEADER_SIZE: u64 = 4 + WAD_SIGNATURE_SIZE as u64 + 8 + 4;
+const WAD_TOC_ENTRY_SIZE: u64 = 32;
+
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// Hash a chunk path the same way the keys of the wad hashtables are hashed
+pub fn hash_chunk_path(path: impl AsRef<str>) -> u64 {
+    xxh64(
+        path.as_ref().replace('\\', "/").to_lowercase().as_bytes(),
+        0,
+    )
+}
+
+/// Data of a single chunk that is going to be written by [`WadBuilder`]
+#[derive(Debug)]
+pub enum WadBuilderChunkData {
+    /// Raw chunk contents, compressed with the compression of the builder
+    Uncompressed(Box<[u8]>),
+    /// Chunk contents which are already compressed, written as-is
+    Compressed {
+        data: Box<[u8]>,
+        compression: WadChunkCompression,
+        uncompressed_size: usize,
+        /// Subchunks of the data, only used by sub-chunked chunks
+        subchunks: Vec<WadSubchunkTocEntry>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct WadBuilderTocEntry {
+    path_hash: u64,
+    data_offset: u32,
+    compressed_size: u32,
+    uncompressed_size: u32,
+    compression: WadChunkCompression,
+    is_duplicated: bool,
+    subchunk_count: u8,
+    start_subchunk: u16,
+    checksum: u64,
+}
+
+/// Writes chunks into a new wad (v3.4) archive
+pub struct WadBuilder {
+    compression: WadChunkCompression,
+    subchunk_toc_path_hash: Option<u64>,
+}
+
+impl WadBuilder {
+    pub fn new(compression: WadChunkCompression) -> Self {
+        Self {
+            compression,
+            subchunk_toc_path_hash: None,
+        }
+    }
+
+    /// Set the path of the `.subchunktoc` chunk, required for writing sub-chunked chunks
+    pub fn with_subchunk_toc_path(mut self, path: impl AsRef<str>) -> Self {
+        self.subchunk_toc_path_hash = Some(hash_chunk_path(path));
+        self
+    }
+
+    pub fn compression(&self) -> WadChunkCompression {
+        self.compression
+    }
+
+    /// Write the chunks into `writer`
+    ///
+    /// Chunks can be provided in any order, the TOC is sorted by path hash before it's written.
+    /// Chunks with identical compressed data are only stored once.
+    pub fn write<TWriter, TChunks>(
+        &self,
+        writer: &mut TWriter,
+        chunks: TChunks,
+        report_progress: impl Fn(f64, Option<&str>) -> eyre::Result<()>,
+    ) -> eyre::Result<()>
+    where
+        TWriter: Write + Seek,
+        TChunks: ExactSizeIterator<Item = eyre::Result<(u64, WadBuilderChunkData)>>,
+    {
+        let chunk_count = chunks.len();
+        tracing::info!("building wad (chunk_count: {})", chunk_count);
+
+        if self.compression == WadChunkCompression::ZstdMulti
+            && self.subchunk_toc_path_hash.is_none()
+        {
+            return Err(eyre!(
+                "sub-chunked compression requires a subchunk toc path"
+            ));
+        }
+
+        // reserve space for the header and the TOC, they get written once we know the offsets
+        let toc_entry_count = chunk_count + self.subchunk_toc_path_hash.is_some() as usize;
+        let data_start = WAD_HEADER_SIZE + WAD_TOC_ENTRY_SIZE * toc_entry_count as u64;
+        writer.seek(SeekFrom::Start(data_start))?;
+
+        let mut toc = Vec::with_capacity(toc_entry_count);
+        let mut subchunk_toc = WadSubchunkToc::default();
+        let mut written_data = HashMap::<(u64, u32), u32>::new();
+        let mut data_offset = data_start;
+
+        for (i, chunk) in chunks.enumerate() {
+            let (path_hash, chunk_data) = chunk?;
+
+            report_progress(
+                i as f64 / chunk_count as f64,
+                Some(&format!("{:x}", path_hash)),
+            )?;
+
+            let (data, compression, uncompressed_size, subchunks) = match chunk_data {
+                WadBuilderChunkData::Uncompressed(data) => {
+                    let (compressed_data, subchunks) = match self.compression {
+                        WadChunkCompression::ZstdMulti => {
+                            compress_subchunks(&data, ZSTD_COMPRESSION_LEVEL)
+                        }
+                        compression => compress_chunk_data(&data, compression)
+                            .map(|compressed_data| (compressed_data, vec![])),
+                    }
+                    .wrap_err(format!(
+                        "failed to compress chunk (path_hash: {:x})",
+                        path_hash
+                    ))?;
+
+                    (compressed_data, self.compression, data.len(), subchunks)
+                }
+                WadBuilderChunkData::Compressed {
+                    data,
+                    compression,
+                    uncompressed_size,
+                    subchunks,
+                } => (data, compression, uncompressed_size, subchunks),
+            };
+
+            if !subchunks.is_empty() && self.subchunk_toc_path_hash.is_none() {
+                return Err(eyre!(
+                    "sub-chunked chunk requires a subchunk toc path (path_hash: {:x})",
+                    path_hash
+                ));
+            }
+
+            let subchunk_count = subchunks.len() as u8;
+            let start_subchunk = to_u16(
+                subchunk_toc.push_chunk_subchunks(subchunks),
+                "subchunk index",
+            )?;
+
+            let checksum = xxh3_64(&data);
+            let compressed_size = to_u32(data.len(), "compressed size")?;
+
+            let (offset, is_duplicated) = match written_data.get(&(checksum, compressed_size)) {
+                Some(offset) => (*offset, true),
+                None => {
+                    let offset = to_u32(data_offset, "data offset")?;
+                    writer.write_all(&data)?;
+                    data_offset += data.len() as u64;
+
+                    written_data.insert((checksum, compressed_size), offset);
+                    (offset, false)
+                }
+            };
+
+            toc.push(WadBuilderTocEntry {
+                path_hash,
+                data_offset: offset,
+                compressed_size,
+                uncompressed_size: to_u32(uncompressed_size, "uncompressed size")?,
+                compression,
+                is_duplicated,
+                subchunk_count,
+                start_subchunk,
+                checksum,
+            });
+        }
+
+        if let Some(subchunk_toc_path_hash) = self.subchunk_toc_path_hash {
+            let data = subchunk_toc.write()?;
+            let offset = to_u32(data_offset, "data offset")?;
+            writer.write_all(&data)?;
+            data_offset += data.len() as u64;
+
+            toc.push(WadBuilderTocEntry {
+                path_hash: subchunk_toc_path_hash,
+                data_offset: offset,
+                compressed_size: to_u32(data.len(), "compressed size")?,
+                uncompressed_size: to_u32(data.len(), "uncompressed size")?,
+                compression: WadChunkCompression::None,
+                is_duplicated: false,
+                subchunk_count: 0,
+                start_subchunk: 0,
+                checksum: xxh3_64(&data),
+            });
+        }
+
+        toc.sort_by_key(|entry| entry.path_hash);
+        if let Some(entry) = toc.windows(2).find(|x| x[0].path_hash == x[1].path_hash) {
+            return Err(eyre!(
+                "duplicate chunk (path_hash: {:x})",
+                entry[0].path_hash
+            ));
+        }
+
+        writer.seek(SeekFrom::Start(0))?;
+        write_header(writer, &toc)?;
+        writer.seek(SeekFrom::Start(data_offset))?;
+        writer.flush()?;
+
+        report_progress(1.0, None)?;
+
+        Ok(())
+    }
+}
+
+fn write_header<TWriter: Write>(
+    writer: &mut TWriter,
+    toc: &[WadBuilderTocEntry],
+) -> eyre::Result<()> {
+    writer.write_all(WAD_MAGIC)?;
+    writer.write_u8(WAD_VERSION_MAJOR)?;
+    writer.write_u8(WAD_VERSION_MINOR)?;
+
+    // we can't sign the archive, the game doesn't verify the signature of mod wads anyway
+    writer.write_all(&[0; WAD_SIGNATURE_SIZE])?;
+
+    // header checksum, not verified by the game
+    writer.write_u64::<LittleEndian>(0)?;
+    writer.write_u32::<LittleEndian>(to_u32(toc.len(), "chunk count")?)?;
+
+    for entry in toc {
+        writer.write_u64::<LittleEndian>(entry.path_hash)?;
+        writer.write_u32::<LittleEndian>(entry.data_offset)?;
+        writer.write_u32::<LittleEndian>(entry.compressed_size)?;
+        writer.write_u32::<LittleEndian>(entry.uncompressed_size)?;
+        writer.write_u8(compression_to_u8(entry.compression) | (entry.subchunk_count << 4))?;
+        writer.write_u8(entry.is_duplicated as u8)?;
+        writer.write_u16::<LittleEndian>(entry.start_subchunk)?;
+        writer.write_u64::<LittleEndian>(entry.checksum)?;
+    }
+
+    Ok(())
+}
+
+pub fn compress_chunk_data(
+    data: &[u8],
+    compression: WadChunkCompression,
+) -> eyre::Result<Box<[u8]>> {
+    match compression {
+        WadChunkCompression::None => Ok(data.into()),
+        WadChunkCompression::GZip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data)?;
+            Ok(encoder.finish()?.into_boxed_slice())
+        }
+        WadChunkCompression::Zstd => Ok(zstd::encode_all(data, ZSTD_COMPRESSION_LEVEL)?.into()),
+        WadChunkCompression::Satellite | WadChunkCompression::ZstdMulti => {
+            Err(eyre!("unsupported chunk compression: {:?}", compression))
+        }
+    }
+}
+
+fn compression_to_u8(compression: WadChunkCompression) -> u8 {
+    match compression {
+        WadChunkCompression::None => 0,
+        WadChunkCompression::GZip => 1,
+        WadChunkCompression::Satellite => 2,
+        WadChunkCompression::Zstd => 3,
+        WadChunkCompression::ZstdMulti => 4,
+    }
+}
+
+fn to_u16(value: usize, name: &str) -> eyre::Result<u16> {
+    value
+        .try_into()
+        .map_err(|_| eyre!("{} does not fit into a wad (value: {})", name, value))
+}
+
+fn to_u32(value: impl TryInto<u32> + Copy + std::fmt::Display, name: &str) -> eyre::Result<u32> {
+    value
+        .try_into()
+        .map_err(|_| eyre!("{} does not fit into a wad (value: {})", name, value))
+}
+
+/// Build a wad from a directory of loose files, each relative path becomes a chunk path
+pub fn build_wad_from_directory<TWriter: Write + Seek>(
+    builder: &WadBuilder,
+    directory: impl AsRef<Path>,
+    writer: &mut TWriter,
+    report_progress: impl Fn(f64, Option<&str>) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    let directory = directory.as_ref();
+    tracing::info!("building wad from directory: {}", directory.display());
+
+    let mut files = Vec::new();
+    for entry in WalkDir::new(directory).follow_links(true) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative_path = entry.path().strip_prefix(directory)?;
+        let relative_path = Utf8Path::from_path(relative_path).ok_or(eyre!(
+            "path is not valid utf-8: {}",
+            relative_path.display()
+        ))?;
+
+        files.push((
+            resolve_chunk_path_hash(relative_path),
+            entry.path().to_path_buf(),
+        ));
+    }
+
+    builder.write(
+        writer,
+        files.into_iter().map(|(path_hash, file_path)| {
+            let data = fs::read(&file_path)
+                .wrap_err(format!("failed to read file: {}", file_path.display()))?;
+
+            Ok((path_hash, WadBuilderChunkData::Uncompressed(data.into())))
+        }),
+        report_progress,
+    )
+}
+
+/// Unknown chunks are extracted into the root directory with their hex path hash as the name,
+/// so those get mapped back to their original hash instead of being re-hashed
+///
+/// Only the names unknown chunks are written with are accepted, 16 hex digits (optionally prefixed
+/// with `.`) or `0x` followed by the hex digits, so regular files like `cafe.png` still get hashed.
+pub fn resolve_chunk_path_hash(relative_path: &Utf8Path) -> u64 {
+    let is_root = relative_path
+        .parent()
+        .is_none_or(|parent| parent.as_str().is_empty());
+
+    if is_root
+        && let Some(file_stem) = relative_path.file_stem()
+        && let Some(path_hash) = parse_hashed_chunk_name(file_stem)
+    {
+        return path_hash;
+    }
+
+    hash_chunk_path(relative_path.as_str())
+}
+
+fn parse_hashed_chunk_name(name: &str) -> Option<u64> {
+    let name = name.strip_prefix('.').unwrap_or(name);
+    let (hex, lengths) = match name.strip_prefix("0x") {
+        Some(hex) => (hex, 1..=16),
+        None => (name, 16..=16),
+    };
+
+    if !lengths.contains(&hex.len()) || !hex.chars().all(|x| x.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    u64::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::wad::tree::{WadTree, WadTreeItem, WadTreePathable},
+        state::WadHashtable,
+    };
+    use league_toolkit::wad::Wad;
+    use std::io::Cursor;
+    use uuid::Uuid;
+
+    fn build(compression: WadChunkCompression, chunks: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = Cursor::new(Vec::new());
+        WadBuilder::new(compression)
+            .write(
+                &mut writer,
+                chunks.iter().map(|(path, data)| {
+                    Ok((
+                        hash_chunk_path(path),
+                        WadBuilderChunkData::Uncompressed((*data).into()),
+                    ))
+                }),
+                |_, _| Ok(()),
+            )
+            .unwrap();
+
+        writer.into_inner()
+    }
+
+    fn assert_round_trip(compression: WadChunkCompression) {
+        let chunks: &[(&str, &[u8])] = &[
+            ("data/characters/aatrox/aatrox.bin", b"PROP some bin data"),
+            (
+                "assets/characters/aatrox/skin0.skn",
+                &[0x33, 0x22, 0x11, 0x00, 0xff],
+            ),
+            ("assets/empty.txt", b""),
+            // same data as the first chunk, stored once
+            ("data/characters/aatrox/copy.bin", b"PROP some bin data"),
+        ];
+
+        let mut wad = Wad::mount(Cursor::new(build(compression, chunks))).unwrap();
+        assert_eq!(wad.chunks().len(), chunks.len());
+
+        let (mut decoder, wad_chunks) = wad.decode();
+        for (path, data) in chunks {
+            let chunk = wad_chunks
+                .get(&hash_chunk_path(path))
+                .unwrap_or_else(|| panic!("missing chunk: {}", path));
+
+            assert_eq!(&*decoder.load_chunk_decompressed(chunk).unwrap(), *data);
+        }
+    }
+
+    #[test]
+    fn round_trips_uncompressed_chunks() {
+        assert_round_trip(WadChunkCompression::None);
+    }
+
+    #[test]
+    fn round_trips_gzip_chunks() {
+        assert_round_trip(WadChunkCompression::GZip);
+    }
+
+    #[test]
+    fn round_trips_zstd_chunks() {
+        assert_round_trip(WadChunkCompression::Zstd);
+    }
+
+    #[test]
+    fn rejects_duplicate_paths() {
+        let mut writer = Cursor::new(Vec::new());
+        let chunks = [0, 1].map(|_| {
+            Ok((
+                hash_chunk_path("a.txt"),
+                WadBuilderChunkData::Uncompressed(b"a".as_slice().into()),
+            ))
+        });
+
+        let result = WadBuilder::new(WadChunkCompression::None).write(
+            &mut writer,
+            chunks.into_iter(),
+            |_, _| Ok(()),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn resolves_hashed_chunk_names() {
+        let path_hash = 0x0123456789abcdef;
+
+        for name in [
+            "0123456789abcdef",
+            ".0123456789abcdef",
+            "0123456789abcdef.dds",
+            "0x123456789abcdef",
+            ".0x123456789abcdef",
+        ] {
+            let expected = match name.contains("0x") {
+                true => 0x123456789abcdef,
+                false => path_hash,
+            };
+            assert_eq!(
+                resolve_chunk_path_hash(Utf8Path::new(name)),
+                expected,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn hashes_regular_file_names() {
+        for name in [
+            "add.txt",
+            "cafe.png",
+            "1.bin",
+            "deadbeef",
+            "data/0123456789abcdef.bin",
+        ] {
+            assert_eq!(
+                resolve_chunk_path_hash(Utf8Path::new(name)),
+                hash_chunk_path(name),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn resolves_unresolved_tree_names() {
+        let path = "data/unknown.bin";
+        let data = build(WadChunkCompression::None, &[(path, b"data")]);
+        let mut wad = Wad::mount(Cursor::new(data)).unwrap();
+        let tree = WadTree::from_wad_fast(
+            &mut wad,
+            Uuid::new_v4(),
+            "test.wad.client",
+            &WadHashtable::default(),
+        )
+        .unwrap();
+
+        let name = tree
+            .item_storage()
+            .values()
+            .find_map(|item| match item {
+                WadTreeItem::File(file) => Some(file.name()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            resolve_chunk_path_hash(Utf8Path::new(&name)),
+            hash_chunk_path(path)
+        );
+    }
+}
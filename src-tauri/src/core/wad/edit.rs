@@ -0,0 +1,148 @@
+use color_eyre::eyre::{self, Context};
+use indexmap::IndexMap;
+use league_toolkit::wad::{WadChunk, WadChunkCompression, WadDecoder};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Read, Seek, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use super::{WadBuilder, WadBuilderChunkData, WadSubchunks};
+
+/// A pending change to a single chunk of a mounted wad
+#[derive(Debug, Clone)]
+pub enum WadChunkEdit {
+    /// Replace the data of an existing chunk, or add a new chunk, with the contents of a file
+    Write {
+        path: Arc<str>,
+        source_path: PathBuf,
+    },
+    /// Remove an existing chunk
+    Delete { path: Arc<str> },
+}
+
+/// Pending edits of a mounted wad, keyed by chunk path hash
+#[derive(Debug, Default)]
+pub struct WadEdits {
+    edits: IndexMap<u64, WadChunkEdit>,
+}
+
+impl WadEdits {
+    pub fn write_chunk(&mut self, path_hash: u64, path: Arc<str>, source_path: PathBuf) {
+        self.edits
+            .insert(path_hash, WadChunkEdit::Write { path, source_path });
+    }
+
+    pub fn delete_chunk(&mut self, path_hash: u64, path: Arc<str>) {
+        self.edits.insert(path_hash, WadChunkEdit::Delete { path });
+    }
+
+    pub fn revert(&mut self, path_hash: u64) -> Option<WadChunkEdit> {
+        self.edits.shift_remove(&path_hash)
+    }
+
+    pub fn get(&self, path_hash: u64) -> Option<&WadChunkEdit> {
+        self.edits.get(&path_hash)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&u64, &WadChunkEdit)> {
+        self.edits.iter()
+    }
+}
+
+enum EditedChunk<'chunks> {
+    Original(&'chunks WadChunk),
+    Written(PathBuf),
+}
+
+/// Write the chunks of a wad with `edits` applied into a new wad
+///
+/// Untouched chunks are copied without recompressing them. Sub-chunked chunks keep their
+/// subchunks if `subchunks` is known, otherwise they get recompressed with the compression of
+/// the builder. The original `.subchunktoc` chunk is skipped, the builder writes a new one.
+pub fn write_edited_wad<TSource: Read + Seek, TWriter: Write + Seek>(
+    builder: &WadBuilder,
+    decoder: &mut WadDecoder<TSource>,
+    chunks: &HashMap<u64, WadChunk>,
+    subchunks: Option<&WadSubchunks>,
+    edits: &WadEdits,
+    writer: &mut TWriter,
+    report_progress: impl Fn(f64, Option<&str>) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    tracing::info!("writing edited wad (edit_count: {})", edits.edits.len());
+
+    let mut edited_chunks = chunks
+        .iter()
+        .filter(|(path_hash, _)| edits.get(**path_hash).is_none())
+        .filter(|(path_hash, _)| {
+            subchunks.is_none_or(|subchunks| subchunks.toc_path_hash() != **path_hash)
+        })
+        .map(|(path_hash, chunk)| (*path_hash, EditedChunk::Original(chunk)))
+        .collect::<Vec<_>>();
+
+    for (path_hash, edit) in edits.iter() {
+        if let WadChunkEdit::Write { source_path, .. } = edit {
+            edited_chunks.push((*path_hash, EditedChunk::Written(source_path.clone())));
+        }
+    }
+
+    builder.write(
+        writer,
+        edited_chunks
+            .into_iter()
+            .map(|(path_hash, chunk)| match chunk {
+                EditedChunk::Original(chunk) => match (
+                    chunk.compression_type(),
+                    subchunks.and_then(|subchunks| subchunks.get(path_hash)),
+                ) {
+                    (WadChunkCompression::ZstdMulti, Some(chunk_subchunks)) => Ok((
+                        path_hash,
+                        WadBuilderChunkData::Compressed {
+                            data: decoder.load_chunk_raw(chunk).wrap_err(format!(
+                                "failed to load chunk (path_hash: {:x})",
+                                path_hash
+                            ))?,
+                            compression: WadChunkCompression::ZstdMulti,
+                            uncompressed_size: chunk.uncompressed_size(),
+                            subchunks: chunk_subchunks
+                                .iter()
+                                .map(|subchunk| subchunk.toc_entry())
+                                .collect(),
+                        },
+                    )),
+                    (WadChunkCompression::ZstdMulti, None) => Ok((
+                        path_hash,
+                        WadBuilderChunkData::Uncompressed(
+                            decoder.load_chunk_decompressed(chunk).wrap_err(format!(
+                                "failed to decompress chunk (path_hash: {:x})",
+                                path_hash
+                            ))?,
+                        ),
+                    )),
+                    (compression, _) => Ok((
+                        path_hash,
+                        WadBuilderChunkData::Compressed {
+                            data: decoder.load_chunk_raw(chunk).wrap_err(format!(
+                                "failed to load chunk (path_hash: {:x})",
+                                path_hash
+                            ))?,
+                            compression,
+                            uncompressed_size: chunk.uncompressed_size(),
+                            subchunks: vec![],
+                        },
+                    )),
+                },
+                EditedChunk::Written(source_path) => Ok((
+                    path_hash,
+                    WadBuilderChunkData::Uncompressed(
+                        fs::read(&source_path)
+                            .wrap_err(format!("failed to read file: {}", source_path.display()))?
+                            .into(),
+                    ),
+                )),
+            }),
+        report_progress,
+    )
+}
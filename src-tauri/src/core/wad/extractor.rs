@@ -194,7 +194,7 @@ fn write_long_filename_chunk(
     extract_directory: impl AsRef<Path>,
     chunk_data: &Box<[u8]>,
 ) -> eyre::Result<()> {
-    let hashed_path = format!(".{:x}", chunk.path_hash());
+    let hashed_path = format!(".{:016x}", chunk.path_hash());
     tracing::warn!(
         "invalid chunk filename, writing as hashed path (chunk_path: {}, hashed_path: {})",
         chunk_path.as_ref().display(),
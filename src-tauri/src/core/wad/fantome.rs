@@ -0,0 +1,209 @@
+use camino::Utf8Path;
+use color_eyre::eyre::{self, Context};
+use league_toolkit::wad::WadChunkCompression;
+use league_toolkit::{
+    file::LeagueFileKind,
+    wad::{WadChunk, WadDecoder},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Read, Seek, Write},
+    path::{Path, PathBuf},
+};
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use super::{WadBuilder, WadBuilderChunkData, resolve_chunk_path_hash};
+use crate::state::WadHashtable;
+
+pub const FANTOME_WAD_DIR: &str = "WAD";
+pub const FANTOME_META_DIR: &str = "META";
+pub const FANTOME_INFO_FILE: &str = "info.json";
+
+/// Contents of `META/info.json` in a fantome package
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct FantomeInfo {
+    pub name: String,
+    pub author: String,
+    pub version: String,
+    pub description: String,
+}
+
+/// Write the chunks as loose files of `WAD/<wad_name>/` into a fantome (zip) package
+pub fn write_fantome<TSource: Read + Seek, TWriter: Write + Seek>(
+    decoder: &mut WadDecoder<TSource>,
+    chunks: &[WadChunk],
+    wad_name: &str,
+    info: &FantomeInfo,
+    wad_hashtable: &WadHashtable,
+    writer: TWriter,
+    report_progress: impl Fn(f64, Option<&str>) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    tracing::info!(
+        "writing fantome (wad_name: {}, chunk_count: {})",
+        wad_name,
+        chunks.len()
+    );
+
+    let mut zip = ZipWriter::new(writer);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file(
+        format!("{}/{}", FANTOME_META_DIR, FANTOME_INFO_FILE),
+        options,
+    )?;
+    serde_json::to_writer_pretty(&mut zip, info)?;
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let chunk_data = decoder.load_chunk_decompressed(chunk).wrap_err(format!(
+            "failed to decompress chunk (path_hash: {:x})",
+            chunk.path_hash()
+        ))?;
+        let chunk_path = resolve_fantome_chunk_path(chunk, &chunk_data, wad_hashtable);
+
+        report_progress(i as f64 / chunks.len() as f64, Some(&chunk_path))?;
+
+        zip.start_file(
+            format!("{}/{}/{}", FANTOME_WAD_DIR, wad_name, chunk_path),
+            options,
+        )?;
+        zip.write_all(&chunk_data)?;
+    }
+
+    zip.finish()?;
+
+    Ok(())
+}
+
+/// Unknown chunks are written with their 16 digit hex path hash as the name, which mod managers
+/// map back to the original hash
+fn resolve_fantome_chunk_path(
+    chunk: &WadChunk,
+    chunk_data: &[u8],
+    wad_hashtable: &WadHashtable,
+) -> String {
+    if let Some(path) = wad_hashtable.get_path(chunk.path_hash()) {
+        return path.replace('\\', "/");
+    }
+
+    match LeagueFileKind::identify_from_bytes(chunk_data).extension() {
+        Some(extension) => format!("{:016x}.{}", chunk.path_hash(), extension),
+        None => format!("{:016x}", chunk.path_hash()),
+    }
+}
+
+/// A wad unpacked from a fantome package
+#[derive(Debug, Clone)]
+pub struct FantomeWad {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Unpack all wads of a fantome (or zip) package into `output_directory`
+///
+/// Packed wads are copied as-is, raw wad folders are built into new wads.
+pub fn unpack_fantome_wads(
+    fantome_path: impl AsRef<Path>,
+    output_directory: impl AsRef<Path>,
+) -> eyre::Result<(Option<FantomeInfo>, Vec<FantomeWad>)> {
+    let fantome_path = fantome_path.as_ref();
+    let output_directory = output_directory.as_ref();
+    tracing::info!("unpacking fantome wads: {}", fantome_path.display());
+
+    let mut archive = ZipArchive::new(File::open(fantome_path).wrap_err(format!(
+        "failed to open fantome: {}",
+        fantome_path.display()
+    ))?)?;
+
+    let mut info = None;
+    let mut wads = Vec::new();
+    let mut raw_wads = HashMap::<String, Vec<(u64, Box<[u8]>)>>::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if !entry.is_file() {
+            continue;
+        }
+
+        let entry_name = entry.name().replace('\\', "/");
+        if entry_name.eq_ignore_ascii_case(&format!("{}/{}", FANTOME_META_DIR, FANTOME_INFO_FILE)) {
+            info =
+                Some(serde_json::from_reader(&mut entry).wrap_err("failed to parse fantome info")?);
+            continue;
+        }
+
+        let Some((wad_name, chunk_path)) = split_fantome_wad_entry(&entry_name) else {
+            continue;
+        };
+
+        match chunk_path {
+            None => {
+                let path = output_directory.join(&wad_name);
+                io::copy(&mut entry, &mut BufWriter::new(File::create(&path)?))?;
+
+                wads.push(FantomeWad {
+                    name: wad_name,
+                    path,
+                });
+            }
+            Some(chunk_path) => {
+                let mut data = Vec::with_capacity(entry.size() as usize);
+                entry.read_to_end(&mut data)?;
+
+                raw_wads.entry(wad_name).or_default().push((
+                    resolve_chunk_path_hash(Utf8Path::new(&chunk_path)),
+                    data.into_boxed_slice(),
+                ));
+            }
+        }
+    }
+
+    // raw chunks are kept uncompressed so building the wad stays fast
+    let builder = WadBuilder::new(WadChunkCompression::None);
+    for (wad_name, chunks) in raw_wads {
+        let path = output_directory.join(&wad_name);
+
+        builder
+            .write(
+                &mut BufWriter::new(File::create(&path)?),
+                chunks.into_iter().map(|(path_hash, data)| {
+                    Ok((path_hash, WadBuilderChunkData::Uncompressed(data)))
+                }),
+                |_, _| Ok(()),
+            )
+            .wrap_err(format!("failed to build raw wad: {}", wad_name))?;
+
+        wads.push(FantomeWad {
+            name: wad_name,
+            path,
+        });
+    }
+
+    Ok((info, wads))
+}
+
+/// Split `WAD/<wad_name>[/<chunk_path>]` into the wad name and the chunk path
+fn split_fantome_wad_entry(entry_name: &str) -> Option<(String, Option<String>)> {
+    let (root, rest) = entry_name.split_once('/')?;
+    if !root.eq_ignore_ascii_case(FANTOME_WAD_DIR) {
+        return None;
+    }
+
+    let (wad_name, chunk_path) = match rest.split_once('/') {
+        Some((wad_name, chunk_path)) => (wad_name, Some(chunk_path.to_string())),
+        None => (rest, None),
+    };
+
+    let lowercase_wad_name = wad_name.to_lowercase();
+    if !lowercase_wad_name.ends_with(".wad.client") && !lowercase_wad_name.ends_with(".wad") {
+        tracing::warn!("skipping invalid fantome wad entry: {}", entry_name);
+        return None;
+    }
+
+    match chunk_path {
+        Some(chunk_path) if chunk_path.is_empty() => None,
+        chunk_path => Some((wad_name.to_string(), chunk_path)),
+    }
+}
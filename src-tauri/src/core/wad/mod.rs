@@ -1,12 +1,21 @@
+mod builder;
+mod edit;
 mod extractor;
+mod fantome;
+mod preview;
+mod subchunk;
+mod toc;
+mod unresolved;
+mod verifier;
 
 pub mod tree;
 
+pub use builder::*;
+pub use edit::*;
 pub use extractor::*;
-use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum WadChunkPreviewType {
-    Image,
-}
+pub use fantome::*;
+pub use preview::*;
+pub use subchunk::*;
+pub use toc::*;
+pub use unresolved::*;
+pub use verifier::*;
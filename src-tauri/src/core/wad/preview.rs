@@ -0,0 +1,105 @@
+use league_toolkit::file::LeagueFileKind;
+use serde::{Deserialize, Serialize};
+
+use crate::core::text;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WadChunkPreviewType {
+    Image,
+    Bin,
+    Hex,
+    Text,
+    /// glb of a 3D model
+    Model,
+}
+
+/// What a previewer gets to look at when deciding whether it supports a chunk
+#[derive(Debug, Clone, Copy)]
+pub struct WadChunkPreviewContext<'a> {
+    pub path: &'a str,
+    /// Identified from the magic bytes of the decompressed data
+    pub kind: LeagueFileKind,
+    /// Decompressed data of the chunk
+    pub data: &'a [u8],
+}
+
+type WadChunkPreviewSupport = Box<dyn Fn(&WadChunkPreviewContext) -> bool + Send + Sync>;
+
+/// Maps chunks to the preview types which can show them
+///
+/// New previewers are added to [`WadChunkPreviewRegistry::default`], preview types are listed in
+/// the order they were added, so the preferred preview of a chunk comes first.
+pub struct WadChunkPreviewRegistry {
+    previewers: Vec<(WadChunkPreviewType, WadChunkPreviewSupport)>,
+}
+
+impl Default for WadChunkPreviewRegistry {
+    fn default() -> Self {
+        Self::new()
+            .with_kinds(
+                WadChunkPreviewType::Image,
+                &[LeagueFileKind::Texture, LeagueFileKind::TextureDds],
+            )
+            .with_kinds(
+                WadChunkPreviewType::Bin,
+                &[
+                    LeagueFileKind::PropertyBin,
+                    LeagueFileKind::PropertyBinOverride,
+                ],
+            )
+            .with_kinds(WadChunkPreviewType::Model, &[LeagueFileKind::SimpleSkin])
+            .with_previewer(WadChunkPreviewType::Text, |context| {
+                matches!(context.kind, LeagueFileKind::Unknown | LeagueFileKind::Svg)
+                    && text::is_text_chunk(context.path, context.data)
+            })
+            // every chunk can be shown as hex
+            .with_previewer(WadChunkPreviewType::Hex, |_| true)
+    }
+}
+
+impl WadChunkPreviewRegistry {
+    /// Create a registry without any previewers
+    pub fn new() -> Self {
+        Self {
+            previewers: Vec::new(),
+        }
+    }
+
+    /// Offer `preview_type` for every chunk which `supports` accepts
+    pub fn with_previewer(
+        mut self,
+        preview_type: WadChunkPreviewType,
+        supports: impl Fn(&WadChunkPreviewContext) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.previewers.push((preview_type, Box::new(supports)));
+        self
+    }
+
+    /// Offer `preview_type` for every chunk of one of `kinds`
+    pub fn with_kinds(
+        self,
+        preview_type: WadChunkPreviewType,
+        kinds: &'static [LeagueFileKind],
+    ) -> Self {
+        self.with_previewer(preview_type, move |context| kinds.contains(&context.kind))
+    }
+
+    /// Get the preview types which support a chunk, `data` is the decompressed data of the chunk
+    pub fn preview_types(&self, path: &str, data: &[u8]) -> Vec<WadChunkPreviewType> {
+        let context = WadChunkPreviewContext {
+            path,
+            kind: LeagueFileKind::identify_from_bytes(data),
+            data,
+        };
+
+        let mut preview_types = Vec::new();
+        for (preview_type, supports) in &self.previewers {
+            if !preview_types.contains(preview_type) && supports(&context) {
+                preview_types.push(*preview_type);
+            }
+        }
+
+        preview_types
+    }
+}
@@ -0,0 +1,295 @@
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use camino::Utf8Path;
+use color_eyre::eyre::{self, Context, eyre};
+use league_toolkit::wad::{WadChunk, WadDecoder};
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Seek, SeekFrom},
+};
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::{WadToc, WadTocEntry, hash_chunk_path};
+
+pub const SUBCHUNK_TOC_EXTENSION: &str = "subchunktoc";
+
+const SUBCHUNK_TOC_ENTRY_SIZE: usize = 16;
+/// The subchunk count of a chunk is stored in 4 bits of its TOC entry
+pub const MAX_CHUNK_SUBCHUNK_COUNT: usize = 15;
+const MIN_SUBCHUNK_SIZE: usize = 64 * 1024;
+
+/// Entry of the `.subchunktoc` chunk of a wad
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WadSubchunkTocEntry {
+    pub compressed_size: u32,
+    pub uncompressed_size: u32,
+    pub checksum: u64,
+}
+
+/// A subchunk (frame) of a sub-chunked chunk, with its offsets inside of the chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WadSubchunk {
+    pub index: usize,
+    pub compressed_offset: usize,
+    pub compressed_size: usize,
+    pub uncompressed_offset: usize,
+    pub uncompressed_size: usize,
+    pub checksum: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WadSubchunkToc {
+    entries: Vec<WadSubchunkTocEntry>,
+}
+
+impl WadSubchunkToc {
+    pub fn read(data: &[u8]) -> eyre::Result<Self> {
+        if !data.len().is_multiple_of(SUBCHUNK_TOC_ENTRY_SIZE) {
+            return Err(eyre!("invalid subchunk toc size: {}", data.len()));
+        }
+
+        let mut reader = Cursor::new(data);
+        let mut entries = Vec::with_capacity(data.len() / SUBCHUNK_TOC_ENTRY_SIZE);
+        for _ in 0..data.len() / SUBCHUNK_TOC_ENTRY_SIZE {
+            entries.push(WadSubchunkTocEntry {
+                compressed_size: reader.read_u32::<LittleEndian>()?,
+                uncompressed_size: reader.read_u32::<LittleEndian>()?,
+                checksum: reader.read_u64::<LittleEndian>()?,
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn write(&self) -> eyre::Result<Box<[u8]>> {
+        let mut data = Vec::with_capacity(self.entries.len() * SUBCHUNK_TOC_ENTRY_SIZE);
+        for entry in &self.entries {
+            data.write_u32::<LittleEndian>(entry.compressed_size)?;
+            data.write_u32::<LittleEndian>(entry.uncompressed_size)?;
+            data.write_u64::<LittleEndian>(entry.checksum)?;
+        }
+
+        Ok(data.into_boxed_slice())
+    }
+
+    pub fn entries(&self) -> &[WadSubchunkTocEntry] {
+        &self.entries
+    }
+
+    /// Append the subchunks of a chunk, returning the index of the first one
+    pub fn push_chunk_subchunks(
+        &mut self,
+        subchunks: impl IntoIterator<Item = WadSubchunkTocEntry>,
+    ) -> usize {
+        let start = self.entries.len();
+        self.entries.extend(subchunks);
+        start
+    }
+
+    /// Get the subchunks of a chunk with their offsets
+    pub fn chunk_subchunks(&self, entry: &WadTocEntry) -> eyre::Result<Vec<WadSubchunk>> {
+        let start = entry.start_subchunk as usize;
+        let end = start + entry.subchunk_count as usize;
+
+        let Some(entries) = self.entries.get(start..end) else {
+            return Err(eyre!(
+                "subchunks out of range (path_hash: {:x}, start: {}, count: {}, toc_len: {})",
+                entry.path_hash,
+                start,
+                entry.subchunk_count,
+                self.entries.len()
+            ));
+        };
+
+        let mut compressed_offset = 0;
+        let mut uncompressed_offset = 0;
+        Ok(entries
+            .iter()
+            .enumerate()
+            .map(|(i, subchunk)| {
+                let subchunk = WadSubchunk {
+                    index: start + i,
+                    compressed_offset,
+                    compressed_size: subchunk.compressed_size as usize,
+                    uncompressed_offset,
+                    uncompressed_size: subchunk.uncompressed_size as usize,
+                    checksum: subchunk.checksum,
+                };
+
+                compressed_offset += subchunk.compressed_size;
+                uncompressed_offset += subchunk.uncompressed_size;
+                subchunk
+            })
+            .collect())
+    }
+}
+
+impl WadSubchunk {
+    pub fn toc_entry(&self) -> WadSubchunkTocEntry {
+        WadSubchunkTocEntry {
+            compressed_size: self.compressed_size as u32,
+            uncompressed_size: self.uncompressed_size as u32,
+            checksum: self.checksum,
+        }
+    }
+}
+
+/// Subchunks of every sub-chunked chunk in a wad, keyed by chunk path hash
+#[derive(Debug, Clone)]
+pub struct WadSubchunks {
+    toc_path_hash: u64,
+    chunks: HashMap<u64, Vec<WadSubchunk>>,
+}
+
+impl WadSubchunks {
+    /// Load the `.subchunktoc` chunk of a wad, returns `None` if the wad doesn't have one
+    pub fn load<TSource: Read + Seek>(
+        decoder: &mut WadDecoder<TSource>,
+        chunks: &HashMap<u64, WadChunk>,
+        toc: &WadToc,
+        wad_path: impl AsRef<Utf8Path>,
+    ) -> eyre::Result<Option<Self>> {
+        let Some(toc_path_hash) = find_subchunk_toc_path_hash(wad_path, chunks) else {
+            return Ok(None);
+        };
+
+        let data = decoder
+            .load_chunk_decompressed(&chunks[&toc_path_hash])
+            .wrap_err("failed to load subchunk toc")?;
+        let subchunk_toc = WadSubchunkToc::read(&data)?;
+
+        let chunks = toc
+            .entries
+            .iter()
+            .filter(|entry| entry.subchunk_count > 0)
+            .map(|entry| Ok((entry.path_hash, subchunk_toc.chunk_subchunks(entry)?)))
+            .collect::<eyre::Result<HashMap<_, _>>>()?;
+
+        tracing::info!(
+            "loaded subchunk toc (path_hash: {:x}, chunk_count: {})",
+            toc_path_hash,
+            chunks.len()
+        );
+
+        Ok(Some(Self {
+            toc_path_hash,
+            chunks,
+        }))
+    }
+
+    pub fn toc_path_hash(&self) -> u64 {
+        self.toc_path_hash
+    }
+
+    pub fn get(&self, path_hash: u64) -> Option<&[WadSubchunk]> {
+        self.chunks.get(&path_hash).map(Vec::as_slice)
+    }
+}
+
+/// Find the path hash of the `.subchunktoc` chunk of a wad
+///
+/// The chunk is named after the path of the wad relative to the game directory, which isn't
+/// known for wads mounted from anywhere, so every suffix of the wad path is tried.
+pub fn find_subchunk_toc_path_hash(
+    wad_path: impl AsRef<Utf8Path>,
+    chunks: &HashMap<u64, WadChunk>,
+) -> Option<u64> {
+    let subchunk_toc_path = subchunk_toc_path(wad_path.as_ref());
+    let components = subchunk_toc_path.split('/').collect::<Vec<_>>();
+
+    (0..components.len())
+        .map(|start| hash_chunk_path(components[start..].join("/")))
+        .find(|path_hash| chunks.contains_key(path_hash))
+}
+
+/// Guess the `.subchunktoc` chunk path for a wad, relative to the game `DATA` directory
+pub fn subchunk_toc_path(wad_path: &Utf8Path) -> String {
+    let components = wad_path
+        .components()
+        .map(|component| component.as_str())
+        .collect::<Vec<_>>();
+    let start = components
+        .iter()
+        .rposition(|component| component.eq_ignore_ascii_case("data"))
+        .unwrap_or(components.len().saturating_sub(1));
+
+    let path = components[start..].join("/").to_lowercase();
+    let path = path.strip_suffix(".client").unwrap_or(&path);
+
+    format!("{}.{}", path, SUBCHUNK_TOC_EXTENSION)
+}
+
+/// Read and decompress a single subchunk of a chunk, only the compressed range of the subchunk is
+/// read from the wad
+pub fn read_subchunk<TSource: Read + Seek>(
+    reader: &mut TSource,
+    chunk: &WadChunk,
+    subchunk: &WadSubchunk,
+) -> eyre::Result<Box<[u8]>> {
+    if subchunk.compressed_offset + subchunk.compressed_size > chunk.compressed_size() {
+        return Err(eyre!("subchunk out of range (index: {})", subchunk.index));
+    }
+
+    reader.seek(SeekFrom::Start(
+        (chunk.data_offset() + subchunk.compressed_offset) as u64,
+    ))?;
+
+    let mut data = Vec::new();
+    reader
+        .take(subchunk.compressed_size as u64)
+        .read_to_end(&mut data)?;
+    if data.len() != subchunk.compressed_size {
+        return Err(eyre!("subchunk out of range (index: {})", subchunk.index));
+    }
+
+    decompress_subchunk(&data, subchunk)
+}
+
+/// Decompress the data of a single subchunk
+///
+/// Subchunks which didn't compress are stored as-is.
+pub fn decompress_subchunk(data: &[u8], subchunk: &WadSubchunk) -> eyre::Result<Box<[u8]>> {
+    if subchunk.compressed_size == subchunk.uncompressed_size {
+        return Ok(data.into());
+    }
+
+    let decompressed = zstd::bulk::decompress(data, subchunk.uncompressed_size)?;
+    if decompressed.len() != subchunk.uncompressed_size {
+        return Err(eyre!(
+            "invalid subchunk size (index: {}, expected: {}, actual: {})",
+            subchunk.index,
+            subchunk.uncompressed_size,
+            decompressed.len()
+        ));
+    }
+
+    Ok(decompressed.into_boxed_slice())
+}
+
+/// Split chunk data into zstd compressed subchunks
+pub fn compress_subchunks(
+    data: &[u8],
+    compression_level: i32,
+) -> eyre::Result<(Box<[u8]>, Vec<WadSubchunkTocEntry>)> {
+    let subchunk_size = MIN_SUBCHUNK_SIZE.max(data.len().div_ceil(MAX_CHUNK_SUBCHUNK_COUNT));
+
+    let mut compressed = Vec::with_capacity(data.len());
+    let mut subchunks = Vec::new();
+    for subchunk_data in data.chunks(subchunk_size) {
+        let compressed_subchunk = zstd::bulk::compress(subchunk_data, compression_level)?;
+
+        // store subchunks that don't get smaller uncompressed
+        let stored_data = match compressed_subchunk.len() < subchunk_data.len() {
+            true => compressed_subchunk.as_slice(),
+            false => subchunk_data,
+        };
+
+        subchunks.push(WadSubchunkTocEntry {
+            compressed_size: stored_data.len() as u32,
+            uncompressed_size: subchunk_data.len() as u32,
+            checksum: xxh3_64(stored_data),
+        });
+        compressed.extend_from_slice(stored_data);
+    }
+
+    Ok((compressed.into_boxed_slice(), subchunks))
+}
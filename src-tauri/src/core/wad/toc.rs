@@ -0,0 +1,160 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use color_eyre::eyre::{self, eyre};
+use league_toolkit::wad::WadChunkCompression;
+use std::io::{Read, Seek, SeekFrom};
+
+const WAD_MAGIC: &[u8; 2] = b"RW";
+
+/// Raw header and TOC of a wad, including the fields `league_toolkit` doesn't expose
+#[derive(Debug, Clone)]
+pub struct WadToc {
+    pub major: u8,
+    pub minor: u8,
+    pub signature: Box<[u8]>,
+    pub checksum: u64,
+    pub toc_offset: u64,
+    pub entries: Vec<WadTocEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WadTocEntry {
+    pub path_hash: u64,
+    pub data_offset: u32,
+    pub compressed_size: u32,
+    pub uncompressed_size: u32,
+    /// Raw compression type, see [`league_toolkit::wad::WadChunkCompression`]
+    pub compression: u8,
+    pub subchunk_count: u8,
+    pub is_duplicated: bool,
+    pub start_subchunk: u16,
+    pub checksum: u64,
+}
+
+/// Kind of checksum stored in the TOC entries of a wad version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WadChecksumKind {
+    None,
+    Sha256,
+    Xxh3,
+}
+
+impl WadToc {
+    pub fn read<TSource: Read + Seek>(reader: &mut TSource) -> eyre::Result<Self> {
+        reader.seek(SeekFrom::Start(0))?;
+
+        let mut magic = [0; 2];
+        reader.read_exact(&mut magic)?;
+        if &magic != WAD_MAGIC {
+            return Err(eyre!("invalid wad magic: {:?}", magic));
+        }
+
+        let major = reader.read_u8()?;
+        let minor = reader.read_u8()?;
+
+        let (signature, checksum, toc_offset, toc_entry_size, chunk_count) = match major {
+            1 => {
+                let toc_offset = reader.read_u16::<LittleEndian>()? as u64;
+                let toc_entry_size = reader.read_u16::<LittleEndian>()? as u64;
+                let chunk_count = reader.read_u32::<LittleEndian>()?;
+
+                (Box::default(), 0, toc_offset, toc_entry_size, chunk_count)
+            }
+            2 => {
+                let signature_size = reader.read_u8()? as usize;
+                let mut signature = [0; 83];
+                reader.read_exact(&mut signature)?;
+                let checksum = reader.read_u64::<LittleEndian>()?;
+
+                let toc_offset = reader.read_u16::<LittleEndian>()? as u64;
+                let toc_entry_size = reader.read_u16::<LittleEndian>()? as u64;
+                let chunk_count = reader.read_u32::<LittleEndian>()?;
+
+                (
+                    signature[..signature_size.min(signature.len())].into(),
+                    checksum,
+                    toc_offset,
+                    toc_entry_size,
+                    chunk_count,
+                )
+            }
+            3 => {
+                let mut signature = vec![0; 256];
+                reader.read_exact(&mut signature)?;
+                let checksum = reader.read_u64::<LittleEndian>()?;
+                let chunk_count = reader.read_u32::<LittleEndian>()?;
+
+                (
+                    signature.into(),
+                    checksum,
+                    reader.stream_position()?,
+                    32,
+                    chunk_count,
+                )
+            }
+            _ => return Err(eyre!("unsupported wad version: {}.{}", major, minor)),
+        };
+
+        let mut entries = Vec::with_capacity(chunk_count as usize);
+        for i in 0..chunk_count as u64 {
+            reader.seek(SeekFrom::Start(toc_offset + i * toc_entry_size))?;
+            entries.push(WadTocEntry::read(reader, major)?);
+        }
+
+        Ok(Self {
+            major,
+            minor,
+            signature,
+            checksum,
+            toc_offset,
+            entries,
+        })
+    }
+
+    pub fn checksum_kind(&self) -> WadChecksumKind {
+        match (self.major, self.minor) {
+            (1, _) => WadChecksumKind::None,
+            (2, _) | (3, 0) => WadChecksumKind::Sha256,
+            _ => WadChecksumKind::Xxh3,
+        }
+    }
+}
+
+impl WadTocEntry {
+    pub fn compression_type(&self) -> Option<WadChunkCompression> {
+        match self.compression {
+            0 => Some(WadChunkCompression::None),
+            1 => Some(WadChunkCompression::GZip),
+            2 => Some(WadChunkCompression::Satellite),
+            3 => Some(WadChunkCompression::Zstd),
+            4 => Some(WadChunkCompression::ZstdMulti),
+            _ => None,
+        }
+    }
+
+    fn read<TSource: Read>(reader: &mut TSource, major: u8) -> eyre::Result<Self> {
+        let path_hash = reader.read_u64::<LittleEndian>()?;
+        let data_offset = reader.read_u32::<LittleEndian>()?;
+        let compressed_size = reader.read_u32::<LittleEndian>()?;
+        let uncompressed_size = reader.read_u32::<LittleEndian>()?;
+
+        let type_subchunk_count = reader.read_u8()?;
+        let is_duplicated = reader.read_u8()? == 1;
+        let start_subchunk = reader.read_u16::<LittleEndian>()?;
+        let checksum = match major {
+            1 => 0,
+            _ => reader.read_u64::<LittleEndian>()?,
+        };
+
+        Ok(Self {
+            path_hash,
+            data_offset,
+            compressed_size,
+            uncompressed_size,
+            compression: type_subchunk_count & 0xF,
+            subchunk_count: type_subchunk_count >> 4,
+            is_duplicated,
+            start_subchunk,
+            checksum,
+        })
+    }
+}
@@ -55,6 +55,34 @@ pub struct WadItemKindPath {
     pub path: Arc<str>,
 }
 
+/// How many chunks of a wad have a path in the hashtable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WadTreeCoverage {
+    pub chunk_count: usize,
+    pub resolved_count: usize,
+}
+
+impl WadTreeCoverage {
+    pub fn unresolved_count(&self) -> usize {
+        self.chunk_count - self.resolved_count
+    }
+
+    /// Percentage of resolved chunks, an empty wad counts as fully resolved
+    pub fn percentage(&self) -> f64 {
+        match self.chunk_count {
+            0 => 100.0,
+            chunk_count => self.resolved_count as f64 / chunk_count as f64 * 100.0,
+        }
+    }
+}
+
+impl std::ops::AddAssign for WadTreeCoverage {
+    fn add_assign(&mut self, other: Self) {
+        self.chunk_count += other.chunk_count;
+        self.resolved_count += other.resolved_count;
+    }
+}
+
 #[derive(Debug)]
 pub struct WadTree {
     wad_id: Uuid,
@@ -62,6 +90,7 @@ pub struct WadTree {
     items: Vec<Uuid>,
     item_storage: HashMap<Uuid, WadTreeItem>,
     chunk_item_ids: HashMap<WadItemKindPath, Uuid>,
+    coverage: WadTreeCoverage,
 }
 
 impl WadTree {
@@ -88,12 +117,19 @@ impl WadTree {
             items: Vec::with_capacity(64), // Root items are usually fewer
             item_storage: HashMap::with_capacity(chunk_count * 2), // Files + directories
             chunk_item_ids: HashMap::with_capacity(chunk_count * 2),
+            coverage: WadTreeCoverage {
+                chunk_count,
+                resolved_count: 0,
+            },
         };
 
         // Create all items
         for (chunk_path_hash, chunk) in chunks.iter() {
-            let path = match hashtable.items().get(chunk_path_hash) {
-                Some(path) => path.clone(),
+            let path = match hashtable.get_path(*chunk_path_hash) {
+                Some(path) => {
+                    tree.coverage.resolved_count += 1;
+                    path
+                }
                 None => Self::guess_chunk_path_with_decompress(*chunk_path_hash, chunk, &mut decoder)?,
             };
 
@@ -129,13 +165,20 @@ impl WadTree {
             items: Vec::with_capacity(64),
             item_storage: HashMap::with_capacity(chunk_count * 2),
             chunk_item_ids: HashMap::with_capacity(chunk_count * 2),
+            coverage: WadTreeCoverage {
+                chunk_count,
+                resolved_count: 0,
+            },
         };
 
         // Create all items - use hashtable only, no decompression
         for (chunk_path_hash, chunk) in chunks.iter() {
-            let path: Arc<str> = match hashtable.items().get(chunk_path_hash) {
-                Some(path) => path.clone(),
-                None => format!("{:#0x}", chunk_path_hash).into(),
+            let path: Arc<str> = match hashtable.get_path(*chunk_path_hash) {
+                Some(path) => {
+                    tree.coverage.resolved_count += 1;
+                    path
+                }
+                None => format!("{:016x}", chunk_path_hash).into(),
             };
 
             tree.create_item_from_chunk(chunk, path)?;
@@ -157,8 +200,8 @@ impl WadTree {
         let file_kind = LeagueFileKind::identify_from_bytes(&data);
 
         match file_kind {
-            LeagueFileKind::Unknown => Ok(format!("{:#0x}", chunk_path_hash).into()),
-            _ => Ok(format!("{:#0x}.{}", chunk_path_hash, file_kind.extension().unwrap()).into()),
+            LeagueFileKind::Unknown => Ok(format!("{:016x}", chunk_path_hash).into()),
+            _ => Ok(format!("{:016x}.{}", chunk_path_hash, file_kind.extension().unwrap()).into()),
         }
     }
 
@@ -327,10 +370,109 @@ impl WadTree {
         self.store_item(path, item);
     }
 
+    /// Carry over item ids, selection and expansion from `previous` (a tree of the same wad built
+    /// against another hashtable) to every item which still exists
+    ///
+    /// Files are matched by their chunk, directories by their path.
+    pub fn inherit_state(&mut self, previous: &WadTree) {
+        let previous_files = previous
+            .item_storage
+            .values()
+            .filter_map(|item| match item {
+                WadTreeItem::File(file) => Some((file.chunk.path_hash(), file)),
+                WadTreeItem::Directory(_) => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut id_map = HashMap::with_capacity(self.item_storage.len());
+        for item in self.item_storage.values_mut() {
+            match item {
+                WadTreeItem::File(file) => {
+                    if let Some(previous_file) = previous_files.get(&file.chunk.path_hash()) {
+                        id_map.insert(file.id, previous_file.id);
+                        file.is_selected = previous_file.is_selected;
+                        file.is_checked = previous_file.is_checked;
+                    }
+                }
+                WadTreeItem::Directory(directory) => {
+                    let key = WadItemKindPath {
+                        kind: WadTreeItemKind::Directory,
+                        path: directory.path.clone(),
+                    };
+                    if let Some(WadTreeItem::Directory(previous_directory)) = previous
+                        .chunk_item_ids
+                        .get(&key)
+                        .and_then(|id| previous.item_storage.get(id))
+                    {
+                        id_map.insert(directory.id, previous_directory.id);
+                        directory.is_selected = previous_directory.is_selected;
+                        directory.is_checked = previous_directory.is_checked;
+                        directory.is_expanded = previous_directory.is_expanded;
+                    }
+                }
+            }
+        }
+
+        let map_id = |id: &Uuid| *id_map.get(id).unwrap_or(id);
+
+        self.items.iter_mut().for_each(|id| *id = map_id(id));
+        self.chunk_item_ids
+            .values_mut()
+            .for_each(|id| *id = map_id(id));
+        self.item_storage = std::mem::take(&mut self.item_storage)
+            .into_values()
+            .map(|mut item| {
+                match &mut item {
+                    WadTreeItem::File(file) => {
+                        file.id = map_id(&file.id);
+                        file.parent_id = file.parent_id.as_ref().map(map_id);
+                    }
+                    WadTreeItem::Directory(directory) => {
+                        directory.id = map_id(&directory.id);
+                        directory.parent_id = directory.parent_id.as_ref().map(map_id);
+                        directory.items.iter_mut().for_each(|id| *id = map_id(id));
+                    }
+                }
+
+                (item.id(), item)
+            })
+            .collect();
+    }
+
+    /// Collect the item if it's a file, or all files nested inside of it if it's a directory
+    pub fn collect_files(&self, item_id: Uuid) -> Vec<&WadTreeFile> {
+        let mut files = Vec::new();
+        let mut pending = vec![item_id];
+
+        while let Some(item_id) = pending.pop() {
+            match self.item_storage.get(&item_id) {
+                Some(WadTreeItem::File(file)) => files.push(file),
+                Some(WadTreeItem::Directory(directory)) => {
+                    pending.extend(directory.items.iter().copied())
+                }
+                None => {}
+            }
+        }
+
+        files
+    }
+
+    /// Find the file of a chunk, this walks every item of the tree
+    pub fn find_file(&self, chunk_path_hash: u64) -> Option<&WadTreeFile> {
+        self.item_storage.values().find_map(|item| match item {
+            WadTreeItem::File(file) if file.chunk.path_hash() == chunk_path_hash => Some(file),
+            _ => None,
+        })
+    }
+
     pub fn wad_path(&self) -> &str {
         &self.wad_path
     }
 
+    pub fn coverage(&self) -> WadTreeCoverage {
+        self.coverage
+    }
+
     pub fn item_storage(&self) -> &HashMap<Uuid, WadTreeItem> {
         &self.item_storage
     }
@@ -0,0 +1,89 @@
+use color_eyre::eyre;
+use league_toolkit::{file::LeagueFileKind, wad::WadDecoder};
+use std::{
+    io::{Read, Seek, Write},
+    sync::Arc,
+};
+
+use super::tree::{WadTree, WadTreeItem};
+use crate::state::WadHashtable;
+
+/// A chunk whose path isn't in the hashtable
+#[derive(Debug, Clone)]
+pub struct UnresolvedWadChunk {
+    pub path_hash: u64,
+    /// Name or path of the wad which contains the chunk
+    pub wad: Arc<str>,
+    pub kind: LeagueFileKind,
+}
+
+/// Collect every chunk of a wad tree which fell back to a hex name, the kind of each chunk
+/// is identified from its decompressed data
+pub fn collect_unresolved_chunks<TSource: Read + Seek>(
+    wad_tree: &WadTree,
+    decoder: &mut WadDecoder<TSource>,
+    wad: Arc<str>,
+    hashtable: &WadHashtable,
+    report_progress: impl Fn(f64) -> eyre::Result<()>,
+) -> eyre::Result<Vec<UnresolvedWadChunk>> {
+    let files = wad_tree
+        .item_storage()
+        .values()
+        .filter_map(|item| match item {
+            WadTreeItem::File(file) => Some(file),
+            WadTreeItem::Directory(_) => None,
+        })
+        .filter(|file| hashtable.get_path(file.chunk().path_hash()).is_none())
+        .collect::<Vec<_>>();
+
+    let mut unresolved_chunks = Vec::with_capacity(files.len());
+    for (i, file) in files.iter().enumerate() {
+        report_progress(i as f64 / files.len() as f64)?;
+
+        let chunk = file.chunk();
+        let kind = match decoder.load_chunk_decompressed(chunk) {
+            Ok(data) => LeagueFileKind::identify_from_bytes(&data),
+            Err(error) => {
+                tracing::warn!(
+                    "failed to decompress chunk (path_hash: {:x}): {}",
+                    chunk.path_hash(),
+                    error
+                );
+                LeagueFileKind::Unknown
+            }
+        };
+
+        unresolved_chunks.push(UnresolvedWadChunk {
+            path_hash: chunk.path_hash(),
+            wad: wad.clone(),
+            kind,
+        });
+    }
+
+    unresolved_chunks.sort_by_key(|chunk| chunk.path_hash);
+
+    Ok(unresolved_chunks)
+}
+
+/// Write unresolved chunks in the hashtable line format, with the path left out and the kind and
+/// the wad in a trailing comment: `<path_hash> # <extension> <wad>`
+///
+/// The kind is written as its file extension, or `unknown` if it couldn't be identified. Loading
+/// such a file as a hashtable doesn't add any entries, since the lines have no path.
+pub fn write_unresolved_chunks(
+    writer: &mut impl Write,
+    unresolved_chunks: &[UnresolvedWadChunk],
+) -> eyre::Result<()> {
+    for chunk in unresolved_chunks {
+        writeln!(
+            writer,
+            "{:016x} # {} {}",
+            chunk.path_hash,
+            chunk.kind.extension().unwrap_or("unknown"),
+            chunk.wad
+        )?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
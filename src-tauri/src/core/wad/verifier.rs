@@ -0,0 +1,100 @@
+use color_eyre::eyre;
+use league_toolkit::wad::{WadChunk, WadDecoder};
+use std::{
+    collections::HashMap,
+    io::{Read, Seek},
+    sync::Arc,
+};
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::{WadChecksumKind, WadToc};
+use crate::state::WadHashtable;
+
+#[derive(Debug, Clone)]
+pub enum WadChunkVerificationError {
+    ChecksumMismatch { expected: u64, actual: u64 },
+    SizeMismatch { expected: usize, actual: usize },
+    LoadFailure { message: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct WadChunkVerificationFailure {
+    pub path_hash: u64,
+    pub path: Arc<str>,
+    pub error: WadChunkVerificationError,
+}
+
+/// Verify the checksum and the decompressed size of every chunk in the TOC
+///
+/// Checksums are only verified for wads using xxh3 checksums (v3.1+).
+pub fn verify_wad_chunks<TSource: Read + Seek>(
+    decoder: &mut WadDecoder<TSource>,
+    chunks: &HashMap<u64, WadChunk>,
+    toc: &WadToc,
+    wad_hashtable: &WadHashtable,
+    report_progress: impl Fn(f64, Option<&str>) -> eyre::Result<()>,
+) -> eyre::Result<Vec<WadChunkVerificationFailure>> {
+    tracing::info!("verifying chunks (chunk_count: {})", toc.entries.len());
+
+    let checksum_kind = toc.checksum_kind();
+    let mut failures = Vec::new();
+
+    for (i, entry) in toc.entries.iter().enumerate() {
+        let path = wad_hashtable.resolve_path(entry.path_hash);
+        report_progress(i as f64 / toc.entries.len() as f64, Some(&path))?;
+
+        let mut report_failure = |error| {
+            tracing::warn!("chunk verification failed ({}): {:?}", path, error);
+
+            failures.push(WadChunkVerificationFailure {
+                path_hash: entry.path_hash,
+                path: path.clone(),
+                error,
+            })
+        };
+
+        let Some(chunk) = chunks.get(&entry.path_hash) else {
+            report_failure(WadChunkVerificationError::LoadFailure {
+                message: "chunk is missing from the mounted wad".into(),
+            });
+            continue;
+        };
+
+        if checksum_kind == WadChecksumKind::Xxh3 {
+            match decoder.load_chunk_raw(chunk) {
+                Ok(raw_data) => {
+                    let checksum = xxh3_64(&raw_data);
+                    if checksum != entry.checksum {
+                        report_failure(WadChunkVerificationError::ChecksumMismatch {
+                            expected: entry.checksum,
+                            actual: checksum,
+                        });
+                    }
+                }
+                Err(error) => {
+                    report_failure(WadChunkVerificationError::LoadFailure {
+                        message: error.to_string(),
+                    });
+                    continue;
+                }
+            }
+        }
+
+        match decoder.load_chunk_decompressed(chunk) {
+            Ok(data) if data.len() != chunk.uncompressed_size() => {
+                report_failure(WadChunkVerificationError::SizeMismatch {
+                    expected: chunk.uncompressed_size(),
+                    actual: data.len(),
+                });
+            }
+            Ok(_) => {}
+            Err(error) => report_failure(WadChunkVerificationError::LoadFailure {
+                message: error.to_string(),
+            }),
+        }
+    }
+
+    report_progress(1.0, None)?;
+
+    Ok(failures)
+}
@@ -12,16 +12,16 @@ mod state;
 mod utils;
 
 use crate::{
-    paths::WAD_HASHTABLES_DIR,
-    state::WadHashtable,
+    paths::{BIN_HASHTABLES_DIR, WAD_HASHTABLES_DIR},
+    state::{BinHashtable, BinHashtableState, WadHashtable},
     utils::fs::try_create_dir,
 };
 use color_eyre::eyre;
 use parking_lot::{lock_api::RwLock, Mutex};
-use paths::{LOGS_DIR, SETTINGS_FILE};
+use paths::{LOGS_DIR, MOD_OVERLAYS_DIR, SETTINGS_FILE};
 use state::{
-    ActionsState, GameExplorer, GameExplorerState, MountedWads, MountedWadsState, Settings,
-    SettingsState, WadHashtableState,
+    ActionsState, ChunkCache, ChunkCacheState, GameExplorer, GameExplorerState, MountedWads,
+    MountedWadsState, Settings, SettingsState, WadHashtableState,
 };
 use std::{collections::HashMap, io::stdout};
 use tauri::{App, AppHandle, Manager};
@@ -45,8 +45,10 @@ fn main() -> eyre::Result<()> {
         .manage(MountedWadsState(Mutex::new(MountedWads::new())))
         .manage(SettingsState(RwLock::new(Settings::default())))
         .manage(WadHashtableState(Mutex::new(WadHashtable::default())))
+        .manage(BinHashtableState(Mutex::new(BinHashtable::default())))
         .manage(ActionsState(RwLock::new(HashMap::default())))
         .manage(GameExplorerState(Mutex::new(GameExplorer::new())))
+        .manage(ChunkCacheState(Mutex::new(ChunkCache::default())))
         .setup(|app| {
             LOG_GUARD.lock().replace(initialize_logging(app.handle())?);
 
@@ -92,6 +94,12 @@ fn initialize_logging(
 fn create_app_directories(app: &mut App) -> eyre::Result<()> {
     info!("creating app directories");
     try_create_dir(app.path().app_data_dir().unwrap().join(WAD_HASHTABLES_DIR))?;
+    try_create_dir(app.path().app_data_dir().unwrap().join(BIN_HASHTABLES_DIR))?;
+
+    // mod overlays are unpacked per session, clean up whatever is left from the last one
+    let mod_overlays_dir = app.path().app_data_dir().unwrap().join(MOD_OVERLAYS_DIR);
+    let _ = std::fs::remove_dir_all(&mod_overlays_dir);
+    try_create_dir(mod_overlays_dir)?;
 
     Ok(())
 }
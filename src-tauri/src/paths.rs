@@ -1,3 +1,12 @@
 pub const WAD_HASHTABLES_DIR: &str = "wad_hashtables";
+/// Hashtable inside of the wad hashtables directory which discovered paths get appended to
+pub const DISCOVERED_WAD_HASHTABLE_FILE: &str = "hashes.discovered.txt";
+/// Hashtable inside of the wad hashtables directory with the paths assigned by the user, it
+/// always takes priority over the other hashtables
+pub const USER_WAD_HASHTABLE_FILE: &str = "hashes.user.txt";
+pub const WAD_HASHTABLES_MANIFEST_FILE: &str = "wad_hashtables.json";
+/// Holds the hashtables of the names used in BIN files, see `BinHashKind::file_name`
+pub const BIN_HASHTABLES_DIR: &str = "bin_hashtables";
+pub const MOD_OVERLAYS_DIR: &str = "mod_overlays";
 pub const LOGS_DIR: &str = "logs";
 pub const SETTINGS_FILE: &str = "settings.json";
@@ -0,0 +1,169 @@
+use crate::api::hashtable::{BinHashtableFileStatus, BinHashtableStatus};
+use crate::core::hashes::{open_hashtable_file, parse_hashtable};
+use color_eyre::eyre;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path, sync::Arc};
+use tracing::info;
+
+/// The kinds of names used in BIN files, each one has its own hashtable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BinHashKind {
+    /// Object paths
+    Entry,
+    /// Property names
+    Field,
+    /// Class names
+    Type,
+    /// Values of hash properties
+    Hash,
+}
+
+impl BinHashKind {
+    pub const ALL: [BinHashKind; 4] = [Self::Entry, Self::Field, Self::Type, Self::Hash];
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Entry => "hashes.binentries.txt",
+            Self::Field => "hashes.binfields.txt",
+            Self::Type => "hashes.bintypes.txt",
+            Self::Hash => "hashes.binhashes.txt",
+        }
+    }
+}
+
+/// Names of the 32-bit FNV-1a hashes used in BIN files
+#[derive(Debug, Default)]
+pub struct BinHashtable {
+    is_loaded: bool,
+    entries: HashMap<u32, Arc<str>>,
+    fields: HashMap<u32, Arc<str>>,
+    types: HashMap<u32, Arc<str>>,
+    hashes: HashMap<u32, Arc<str>>,
+    /// Diagnostics of every loaded file, in load order
+    files: Vec<BinHashtableFileStatus>,
+}
+
+impl BinHashtable {
+    pub fn get(&self, kind: BinHashKind, hash: u32) -> Option<Arc<str>> {
+        self.items(kind).get(&hash).cloned()
+    }
+
+    pub fn resolve(&self, kind: BinHashKind, hash: u32) -> Arc<str> {
+        self.get(kind, hash)
+            .unwrap_or_else(|| format!("{:#010x}", hash).into())
+    }
+
+    pub fn clear(&mut self) {
+        self.is_loaded = false;
+        self.files.clear();
+        for kind in BinHashKind::ALL {
+            self.items_mut(kind).clear();
+        }
+    }
+
+    /// Load the hashtables of every kind from `dir`, missing ones are skipped
+    ///
+    /// Files which fail to load are skipped and reported through [`BinHashtable::status`].
+    pub fn add_from_dir(&mut self, dir: impl AsRef<Path>) -> eyre::Result<()> {
+        let dir = dir.as_ref();
+        info!("loading bin hashtables from dir: {:?}", dir);
+
+        for kind in BinHashKind::ALL {
+            let path = dir.join(kind.file_name());
+            if !path.is_file() {
+                tracing::warn!("bin hashtable missing: {:?}", path);
+                continue;
+            }
+
+            info!("loading bin hashtable: {:?}", path);
+            let file_status = self.add_from_file(kind, &path).unwrap_or_else(|error| {
+                tracing::error!("failed to load bin hashtable: {:?} ({:?})", path, error);
+                BinHashtableFileStatus {
+                    kind,
+                    name: kind.file_name().to_string(),
+                    entry_count: 0,
+                    invalid_line_count: 0,
+                    error: Some(error.to_string()),
+                }
+            });
+
+            self.files.push(file_status);
+        }
+
+        info!("loaded");
+        self.is_loaded = true;
+
+        Ok(())
+    }
+
+    /// Load a hashtable file of `kind`, nothing is added if the file can't be read completely
+    pub fn add_from_file(
+        &mut self,
+        kind: BinHashKind,
+        path: impl AsRef<Path>,
+    ) -> eyre::Result<BinHashtableFileStatus> {
+        let path = path.as_ref();
+        let mut items = HashMap::new();
+        let mut invalid_hash_count = 0;
+
+        let stats = parse_hashtable(
+            open_hashtable_file(path)?,
+            |hash, name| match u32::try_from(hash) {
+                Ok(hash) => {
+                    items.insert(hash, name.into());
+                }
+                Err(_) => invalid_hash_count += 1,
+            },
+        )?;
+
+        if invalid_hash_count > 0 {
+            tracing::warn!("skipped {} hashes wider than 32 bits", invalid_hash_count);
+        }
+
+        self.items_mut(kind).extend(items);
+
+        Ok(BinHashtableFileStatus {
+            kind,
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            entry_count: stats.entry_count - invalid_hash_count,
+            invalid_line_count: stats.invalid_line_count + invalid_hash_count,
+            error: None,
+        })
+    }
+
+    pub fn status(&self) -> BinHashtableStatus {
+        BinHashtableStatus {
+            is_loaded: self.is_loaded,
+            entry_count: self.entries.len(),
+            field_count: self.fields.len(),
+            type_count: self.types.len(),
+            hash_count: self.hashes.len(),
+            files: self.files.clone(),
+        }
+    }
+
+    fn items(&self, kind: BinHashKind) -> &HashMap<u32, Arc<str>> {
+        match kind {
+            BinHashKind::Entry => &self.entries,
+            BinHashKind::Field => &self.fields,
+            BinHashKind::Type => &self.types,
+            BinHashKind::Hash => &self.hashes,
+        }
+    }
+
+    fn items_mut(&mut self, kind: BinHashKind) -> &mut HashMap<u32, Arc<str>> {
+        match kind {
+            BinHashKind::Entry => &mut self.entries,
+            BinHashKind::Field => &mut self.fields,
+            BinHashKind::Type => &mut self.types,
+            BinHashKind::Hash => &mut self.hashes,
+        }
+    }
+}
+
+pub struct BinHashtableState(pub Mutex<BinHashtable>);
@@ -0,0 +1,109 @@
+use color_eyre::eyre;
+use indexmap::IndexMap;
+use league_toolkit::wad::WadChunk;
+use parking_lot::Mutex;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Total size of the decompressed chunks kept in the cache
+const CHUNK_CACHE_CAPACITY: usize = 256 * 1024 * 1024;
+
+/// Identifies a chunk of a wad, the data offset makes sure a chunk which was rewritten by saving
+/// the wad doesn't hit the old data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChunkCacheKey {
+    wad_id: Uuid,
+    path_hash: u64,
+    data_offset: usize,
+}
+
+impl ChunkCacheKey {
+    pub fn new(wad_id: Uuid, chunk: &WadChunk) -> Self {
+        Self {
+            wad_id,
+            path_hash: chunk.path_hash(),
+            data_offset: chunk.data_offset(),
+        }
+    }
+}
+
+/// Least recently used cache of decompressed chunks, so paged reads of a chunk only decompress
+/// it once
+#[derive(Debug)]
+pub struct ChunkCache {
+    /// Ordered from least to most recently used
+    items: IndexMap<ChunkCacheKey, Arc<[u8]>>,
+    size: usize,
+    capacity: usize,
+}
+
+impl Default for ChunkCache {
+    fn default() -> Self {
+        Self::new(CHUNK_CACHE_CAPACITY)
+    }
+}
+
+impl ChunkCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            items: IndexMap::default(),
+            size: 0,
+            capacity,
+        }
+    }
+
+    pub fn get(&mut self, key: &ChunkCacheKey) -> Option<Arc<[u8]>> {
+        let index = self.items.get_index_of(key)?;
+        let last_index = self.items.len() - 1;
+        self.items.move_index(index, last_index);
+
+        self.items
+            .get_index(last_index)
+            .map(|(_, data)| data.clone())
+    }
+
+    /// Get the data of a chunk, loading and inserting it with `load` if it isn't cached
+    pub fn get_or_try_insert_with(
+        &mut self,
+        key: ChunkCacheKey,
+        load: impl FnOnce() -> eyre::Result<Box<[u8]>>,
+    ) -> eyre::Result<Arc<[u8]>> {
+        if let Some(data) = self.get(&key) {
+            return Ok(data);
+        }
+
+        let data = Arc::<[u8]>::from(load()?);
+        self.insert(key, data.clone());
+
+        Ok(data)
+    }
+
+    /// Insert the data of a chunk, evicting the least recently used chunks until the cache fits
+    /// its capacity again. The inserted chunk itself is always kept.
+    pub fn insert(&mut self, key: ChunkCacheKey, data: Arc<[u8]>) {
+        if let Some(previous) = self.items.shift_remove(&key) {
+            self.size -= previous.len();
+        }
+        self.size += data.len();
+        self.items.insert(key, data);
+
+        while self.size > self.capacity && self.items.len() > 1 {
+            if let Some((_, data)) = self.items.shift_remove_index(0) {
+                self.size -= data.len();
+            }
+        }
+    }
+
+    /// Remove every chunk of a wad, e.g. when it gets unmounted
+    pub fn remove_wad(&mut self, wad_id: Uuid) {
+        self.items.retain(|key, _| key.wad_id != wad_id);
+        self.size = self.items.values().map(|data| data.len()).sum();
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.size = 0;
+    }
+}
+
+pub struct ChunkCacheState(pub Mutex<ChunkCache>);
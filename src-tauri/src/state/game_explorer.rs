@@ -1,14 +1,27 @@
 use camino::{Utf8Path, Utf8PathBuf};
+use color_eyre::eyre::{self, Context};
 use indexmap::IndexMap;
 use league_toolkit::wad::Wad;
 use parking_lot::Mutex;
 use rayon::prelude::*;
-use std::{collections::HashMap, fs::File, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::File,
+    sync::Arc,
+};
 use tracing::info;
 use uuid::Uuid;
 use walkdir::WalkDir;
 
-use crate::core::wad::tree::{WadTree, WadTreeError, WadTreeItem, WadTreeParent, WadTreePathable};
+use crate::{
+    core::wad::{
+        self,
+        tree::{
+            WadTree, WadTreeCoverage, WadTreeError, WadTreeItem, WadTreeParent, WadTreePathable,
+        },
+    },
+    utils::fs::try_create_dir,
+};
 
 use super::WadHashtable;
 
@@ -20,7 +33,12 @@ use super::WadHashtable;
 #[derive(Debug, Clone)]
 pub enum MergedItemRef {
     /// Reference to a file in a specific WAD
-    File { wad_id: Uuid, item_id: Uuid },
+    File {
+        wad_id: Uuid,
+        item_id: Uuid,
+        /// Whether a file from a mod overlay replaces this file
+        is_overridden: bool,
+    },
     /// Reference to a merged directory (virtual)
     Directory(Uuid),
 }
@@ -43,6 +61,10 @@ pub struct MergedIndex {
     directories: HashMap<Uuid, MergedDirectory>,
     /// Path -> directory ID lookup (for merging)
     path_to_dir: HashMap<Arc<str>, Uuid>,
+    /// Chunk path hash -> (wad_id, item_id) of every file with that chunk
+    file_refs: HashMap<u64, Vec<(Uuid, Uuid)>>,
+    /// Path -> directory ID lookup of the index this one replaces, only used while building
+    previous_path_to_dir: HashMap<Arc<str>, Uuid>,
 }
 
 impl MergedIndex {
@@ -51,25 +73,45 @@ impl MergedIndex {
             root_items: Vec::new(),
             directories: HashMap::new(),
             path_to_dir: HashMap::new(),
+            file_refs: HashMap::new(),
+            previous_path_to_dir: HashMap::new(),
         }
     }
 
     /// Build merged index from multiple WAD trees
-    pub fn build(wad_trees: &IndexMap<Uuid, WadTree>) -> Self {
+    /// Overlay WADs are added last (in order) and override files with the same chunk.
+    /// Directories which also exist in `previous` keep their ID.
+    pub fn build(
+        wad_trees: &IndexMap<Uuid, WadTree>,
+        overlay_wad_ids: &[Uuid],
+        previous: Option<MergedIndex>,
+    ) -> Self {
         let mut index = Self::new();
+        if let Some(previous) = previous {
+            index.previous_path_to_dir = previous.path_to_dir;
+        }
 
         for (wad_id, tree) in wad_trees.iter() {
-            index.add_wad_tree(*wad_id, tree);
+            if !overlay_wad_ids.contains(wad_id) {
+                index.add_wad_tree(*wad_id, tree, false);
+            }
+        }
+
+        for wad_id in overlay_wad_ids {
+            if let Some(tree) = wad_trees.get(wad_id) {
+                index.add_wad_tree(*wad_id, tree, true);
+            }
         }
 
         index.sort_all();
+        index.previous_path_to_dir = HashMap::new();
         index
     }
 
-    fn add_wad_tree(&mut self, wad_id: Uuid, tree: &WadTree) {
+    fn add_wad_tree(&mut self, wad_id: Uuid, tree: &WadTree, is_overlay: bool) {
         for item_id in tree.items().iter() {
             if let Some(item) = tree.item_storage().get(item_id) {
-                self.add_item_recursive(wad_id, tree, item, None);
+                self.add_item_recursive(wad_id, tree, item, None, is_overlay);
             }
         }
     }
@@ -80,12 +122,24 @@ impl MergedIndex {
         tree: &WadTree,
         item: &WadTreeItem,
         parent_dir_id: Option<Uuid>,
+        is_overlay: bool,
     ) {
         match item {
             WadTreeItem::File(file) => {
+                let chunk_path_hash = file.chunk().path_hash();
+                if is_overlay {
+                    self.flag_overridden(chunk_path_hash, parent_dir_id);
+                }
+
+                self.file_refs
+                    .entry(chunk_path_hash)
+                    .or_default()
+                    .push((wad_id, file.id()));
+
                 let file_ref = MergedItemRef::File {
                     wad_id,
                     item_id: file.id(),
+                    is_overridden: false,
                 };
 
                 if let Some(parent_id) = parent_dir_id {
@@ -105,7 +159,11 @@ impl MergedIndex {
                 } else {
                     // Create new merged directory
                     let new_dir = MergedDirectory {
-                        id: Uuid::new_v4(),
+                        id: self
+                            .previous_path_to_dir
+                            .get(&path)
+                            .copied()
+                            .unwrap_or_else(Uuid::new_v4),
                         name: dir.name(),
                         path: path.clone(),
                         items: Vec::new(),
@@ -131,13 +189,42 @@ impl MergedIndex {
                 // Process children
                 for child_id in dir.items().iter() {
                     if let Some(child) = tree.item_storage().get(child_id) {
-                        self.add_item_recursive(wad_id, tree, child, Some(dir_id));
+                        self.add_item_recursive(wad_id, tree, child, Some(dir_id), is_overlay);
                     }
                 }
             }
         }
     }
 
+    /// Flag all files with the chunk as overridden, files with the same chunk always share
+    /// the same merged parent directory since directories are merged by path
+    fn flag_overridden(&mut self, chunk_path_hash: u64, parent_dir_id: Option<Uuid>) {
+        let Some(file_refs) = self.file_refs.get(&chunk_path_hash) else {
+            return;
+        };
+        let file_refs: HashSet<(Uuid, Uuid)> = file_refs.iter().copied().collect();
+
+        let items = match parent_dir_id {
+            Some(parent_id) => match self.directories.get_mut(&parent_id) {
+                Some(dir) => &mut dir.items,
+                None => return,
+            },
+            None => &mut self.root_items,
+        };
+
+        for item in items.iter_mut() {
+            if let MergedItemRef::File {
+                wad_id,
+                item_id,
+                is_overridden,
+            } = item
+                && file_refs.contains(&(*wad_id, *item_id))
+            {
+                *is_overridden = true;
+            }
+        }
+    }
+
     fn sort_all(&mut self) {
         // Build a name lookup for sorting
         let dir_names: HashMap<Uuid, Arc<str>> = self
@@ -190,6 +277,30 @@ impl MergedIndex {
     pub fn directories(&self) -> &HashMap<Uuid, MergedDirectory> {
         &self.directories
     }
+
+    /// Get the (wad_id, item_id) of every file with the chunk, base game files first
+    pub fn get_file_refs(&self, chunk_path_hash: u64) -> &[(Uuid, Uuid)] {
+        self.file_refs
+            .get(&chunk_path_hash)
+            .map(|refs| refs.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+// ============================================================================
+// Mod Overlays
+// ============================================================================
+
+/// A mod package mounted on top of the game explorer
+#[derive(Debug, Clone)]
+pub struct ModOverlay {
+    pub id: Uuid,
+    pub name: Arc<str>,
+    pub path: Utf8PathBuf,
+    /// Directory containing the WADs unpacked from the mod
+    pub unpack_directory: Utf8PathBuf,
+    /// The overlay WADs, in the order they were mounted
+    pub wad_ids: Vec<Uuid>,
 }
 
 // ============================================================================
@@ -210,6 +321,8 @@ pub struct GameExplorer {
     wad_metadata: HashMap<Uuid, (Utf8PathBuf, Arc<str>)>,
     /// Merged index for unified view
     merged_index: Option<MergedIndex>,
+    /// Mounted mod overlays (overlay_id -> overlay), later overlays take priority
+    mod_overlays: IndexMap<Uuid, ModOverlay>,
 }
 
 impl GameExplorer {
@@ -221,6 +334,7 @@ impl GameExplorer {
             wads: HashMap::new(),
             wad_metadata: HashMap::new(),
             merged_index: None,
+            mod_overlays: IndexMap::new(),
         }
     }
 
@@ -238,6 +352,7 @@ impl GameExplorer {
         let start_time = std::time::Instant::now();
 
         // Clear existing state
+        self.clear_mod_overlays();
         self.wad_trees.clear();
         self.wads.clear();
         self.wad_metadata.clear();
@@ -319,7 +434,7 @@ impl GameExplorer {
 
         // Build merged index
         let index_start = std::time::Instant::now();
-        self.merged_index = Some(MergedIndex::build(&self.wad_trees));
+        self.rebuild_merged_index();
         info!("Built merged index in {:?}", index_start.elapsed());
 
         self.is_initialized = true;
@@ -333,6 +448,265 @@ impl GameExplorer {
         Ok(())
     }
 
+    /// Mount a mod package (.fantome / .zip) as an overlay on top of the game files
+    pub fn mount_mod_overlay(
+        &mut self,
+        mod_path: &Utf8Path,
+        overlays_directory: &Utf8Path,
+        hashtable: &WadHashtable,
+    ) -> eyre::Result<Uuid> {
+        let overlay_id = Uuid::new_v4();
+        info!(
+            "mounting mod overlay (id: {}, path: {})",
+            overlay_id, mod_path
+        );
+
+        let unpack_directory = overlays_directory.join(overlay_id.to_string());
+        try_create_dir(&unpack_directory)?;
+
+        let (mod_info, mod_wads) = match wad::unpack_fantome_wads(mod_path, &unpack_directory) {
+            Ok(result) => result,
+            Err(error) => {
+                let _ = std::fs::remove_dir_all(&unpack_directory);
+                return Err(error);
+            }
+        };
+
+        let mut overlay = ModOverlay {
+            id: overlay_id,
+            name: mod_info
+                .map(|info| info.name)
+                .or_else(|| mod_path.file_stem().map(|x| x.to_string()))
+                .unwrap_or_default()
+                .into(),
+            path: mod_path.to_path_buf(),
+            unpack_directory,
+            wad_ids: Vec::with_capacity(mod_wads.len()),
+        };
+
+        // mount every wad before touching the explorer, so a broken wad doesn't leave half of the
+        // overlay behind
+        let mounted_wads = mod_wads
+            .into_iter()
+            .map(|mod_wad| {
+                let wad_id = Uuid::new_v4();
+                let wad_path = Utf8PathBuf::from_path_buf(mod_wad.path)
+                    .map_err(|path| eyre::eyre!("invalid wad path: {}", path.display()))?;
+
+                let mut wad = Wad::mount(
+                    File::open(&wad_path).wrap_err(format!("failed to open wad: {}", wad_path))?,
+                )?;
+                let tree = WadTree::from_wad(&mut wad, wad_id, wad_path.as_str(), hashtable)?;
+
+                Ok((wad_id, wad, tree, wad_path, mod_wad.name))
+            })
+            .collect::<eyre::Result<Vec<_>>>();
+
+        let mounted_wads = match mounted_wads {
+            Ok(mounted_wads) => mounted_wads,
+            Err(error) => {
+                let _ = std::fs::remove_dir_all(&overlay.unpack_directory);
+                return Err(error);
+            }
+        };
+
+        for (wad_id, wad, tree, wad_path, name) in mounted_wads {
+            self.wad_trees.insert(wad_id, tree);
+            self.wads.insert(wad_id, wad);
+            self.wad_metadata.insert(wad_id, (wad_path, name.into()));
+            overlay.wad_ids.push(wad_id);
+        }
+
+        self.mod_overlays.insert(overlay_id, overlay);
+        self.rebuild_merged_index();
+
+        Ok(overlay_id)
+    }
+
+    pub fn unmount_mod_overlay(&mut self, overlay_id: Uuid) {
+        let Some(overlay) = self.mod_overlays.shift_remove(&overlay_id) else {
+            return;
+        };
+
+        info!("unmounting mod overlay (id: {})", overlay_id);
+
+        self.remove_mod_overlay_wads(&overlay);
+        self.rebuild_merged_index();
+    }
+
+    fn clear_mod_overlays(&mut self) {
+        for (_, overlay) in std::mem::take(&mut self.mod_overlays) {
+            self.remove_mod_overlay_wads(&overlay);
+        }
+    }
+
+    fn remove_mod_overlay_wads(&mut self, overlay: &ModOverlay) {
+        for wad_id in &overlay.wad_ids {
+            self.wad_trees.shift_remove(wad_id);
+            self.wads.remove(wad_id);
+            self.wad_metadata.remove(wad_id);
+        }
+
+        if let Err(error) = std::fs::remove_dir_all(&overlay.unpack_directory) {
+            tracing::warn!(
+                "failed to remove mod overlay directory {}: {}",
+                overlay.unpack_directory,
+                error
+            );
+        }
+    }
+
+    /// Rebuild the trees of all mounted WADs against `hashtable`, keeping the state of items
+    /// which still exist
+    ///
+    /// If `path_hashes` is set, only WADs which contain one of the chunks get rebuilt.
+    pub fn rebuild_wad_trees(
+        &mut self,
+        hashtable: &WadHashtable,
+        path_hashes: Option<&HashSet<u64>>,
+    ) -> Result<(), WadTreeError> {
+        info!(
+            "rebuilding game explorer wad trees ({} WADs)",
+            self.wad_trees.len()
+        );
+        let start_time = std::time::Instant::now();
+
+        let overlay_wad_ids = self.overlay_wad_ids();
+        let wad_trees = &self.wad_trees;
+        let rebuilt_trees = self
+            .wads
+            .par_iter_mut()
+            .filter(|(_, wad)| {
+                path_hashes.is_none_or(|path_hashes| {
+                    wad.chunks()
+                        .keys()
+                        .any(|path_hash| path_hashes.contains(path_hash))
+                })
+            })
+            .filter_map(|(wad_id, wad)| {
+                let wad_tree = wad_trees.get(wad_id)?;
+
+                // same as when mounting, overlay WADs are small enough to identify unknown chunks
+                let rebuilt_tree = match overlay_wad_ids.contains(wad_id) {
+                    true => WadTree::from_wad(wad, *wad_id, wad_tree.wad_path(), hashtable),
+                    false => WadTree::from_wad_fast(wad, *wad_id, wad_tree.wad_path(), hashtable),
+                };
+
+                Some(rebuilt_tree.map(|mut rebuilt_tree| {
+                    rebuilt_tree.inherit_state(wad_tree);
+                    (*wad_id, rebuilt_tree)
+                }))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (wad_id, rebuilt_tree) in rebuilt_trees {
+            if let Some(wad_tree) = self.wad_trees.get_mut(&wad_id) {
+                *wad_tree = rebuilt_tree;
+            }
+        }
+
+        self.rebuild_merged_index();
+
+        info!(
+            "Rebuilt game explorer wad trees in {:?}",
+            start_time.elapsed()
+        );
+
+        Ok(())
+    }
+
+    /// Collect the chunks of every mounted WAD which aren't in `hashtable`, WADs are named by
+    /// their path relative to the league directory
+    pub fn collect_unresolved_chunks(
+        &mut self,
+        hashtable: &WadHashtable,
+        report_progress: impl Fn(f64) -> eyre::Result<()>,
+    ) -> eyre::Result<Vec<wad::UnresolvedWadChunk>> {
+        let wad_count = self.wad_trees.len();
+        let mut unresolved_chunks = Vec::new();
+
+        for (i, (wad_id, wad_tree)) in self.wad_trees.iter().enumerate() {
+            let Some(wad) = self.wads.get_mut(wad_id) else {
+                continue;
+            };
+
+            let wad_name: Arc<str> = match self.wad_metadata.get(wad_id) {
+                Some((wad_path, wad_name)) => self
+                    .base_path
+                    .as_deref()
+                    .and_then(|base_path| wad_path.strip_prefix(base_path).ok())
+                    .map(|wad_path| wad_path.as_str().into())
+                    .unwrap_or(wad_name.clone()),
+                None => wad_tree.wad_path().into(),
+            };
+
+            let (mut decoder, _) = wad.decode();
+            unresolved_chunks.extend(wad::collect_unresolved_chunks(
+                wad_tree,
+                &mut decoder,
+                wad_name,
+                hashtable,
+                |progress| report_progress((i as f64 + progress) / wad_count as f64),
+            )?);
+        }
+
+        Ok(unresolved_chunks)
+    }
+
+    /// Resolution coverage over all mounted WADs
+    pub fn coverage(&self) -> WadTreeCoverage {
+        let mut coverage = WadTreeCoverage::default();
+        for wad_tree in self.wad_trees.values() {
+            coverage += wad_tree.coverage();
+        }
+
+        coverage
+    }
+
+    /// Resolution coverage grouped by the directory of each WAD, relative to the league
+    /// directory, along with the amount of WADs in the directory
+    pub fn coverage_by_directory(&self) -> BTreeMap<Utf8PathBuf, (usize, WadTreeCoverage)> {
+        let mut directories = BTreeMap::<Utf8PathBuf, (usize, WadTreeCoverage)>::new();
+
+        for (wad_id, wad_tree) in self.wad_trees.iter() {
+            let wad_path = self
+                .wad_metadata
+                .get(wad_id)
+                .map(|(wad_path, _)| wad_path.as_path())
+                .unwrap_or(Utf8Path::new(wad_tree.wad_path()));
+            let wad_path = self
+                .base_path
+                .as_deref()
+                .and_then(|base_path| wad_path.strip_prefix(base_path).ok())
+                .unwrap_or(wad_path);
+
+            let (wad_count, coverage) = directories
+                .entry(wad_path.parent().unwrap_or(Utf8Path::new("")).to_path_buf())
+                .or_default();
+            *wad_count += 1;
+            *coverage += wad_tree.coverage();
+        }
+
+        directories
+    }
+
+    fn overlay_wad_ids(&self) -> Vec<Uuid> {
+        self.mod_overlays
+            .values()
+            .flat_map(|overlay| overlay.wad_ids.iter().copied())
+            .collect()
+    }
+
+    fn rebuild_merged_index(&mut self) {
+        let overlay_wad_ids = self.overlay_wad_ids();
+
+        self.merged_index = Some(MergedIndex::build(
+            &self.wad_trees,
+            &overlay_wad_ids,
+            self.merged_index.take(),
+        ));
+    }
+
     // Accessors
 
     pub fn is_initialized(&self) -> bool {
@@ -359,6 +733,13 @@ impl GameExplorer {
         &mut self.wads
     }
 
+    pub fn get_wad_mut(&mut self, wad_id: Uuid) -> Option<(&WadTree, &mut Wad<File>)> {
+        match (self.wad_trees.get(&wad_id), self.wads.get_mut(&wad_id)) {
+            (Some(wad_tree), Some(wad)) => Some((wad_tree, wad)),
+            _ => None,
+        }
+    }
+
     pub fn wad_metadata(&self) -> &HashMap<Uuid, (Utf8PathBuf, Arc<str>)> {
         &self.wad_metadata
     }
@@ -371,6 +752,17 @@ impl GameExplorer {
     pub fn get_wad_name(&self, wad_id: &Uuid) -> Option<&str> {
         self.wad_metadata.get(wad_id).map(|(_, name)| name.as_ref())
     }
+
+    pub fn mod_overlays(&self) -> &IndexMap<Uuid, ModOverlay> {
+        &self.mod_overlays
+    }
+
+    /// Get the mod overlay a WAD belongs to
+    pub fn get_wad_mod_overlay(&self, wad_id: &Uuid) -> Option<&ModOverlay> {
+        self.mod_overlays
+            .values()
+            .find(|overlay| overlay.wad_ids.contains(wad_id))
+    }
 }
 
 pub struct GameExplorerState(pub Mutex<GameExplorer>);
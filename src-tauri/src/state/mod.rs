@@ -1,4 +1,6 @@
 mod actions;
+mod bin_hashtable;
+mod chunk_cache;
 mod game_explorer;
 mod http_client;
 mod mounted_wads;
@@ -6,6 +8,8 @@ mod settings;
 mod wad_hashtable;
 
 pub use actions::*;
+pub use bin_hashtable::*;
+pub use chunk_cache::*;
 pub use game_explorer::*;
 pub use http_client::*;
 pub use mounted_wads::*;
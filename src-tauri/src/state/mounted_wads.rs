@@ -1,18 +1,29 @@
+use color_eyre::eyre::{self, Context, eyre};
 use indexmap::IndexMap;
 use league_toolkit::wad::Wad;
 use parking_lot::Mutex;
-use std::{collections::HashMap, fs::File, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    sync::Arc,
+};
 
 use tracing::info;
 use uuid::Uuid;
 
-use crate::core::wad::tree::{WadTree, WadTreeError};
+use crate::core::wad::{
+    WadEdits, WadSubchunks, WadToc,
+    tree::{WadTree, WadTreeError},
+};
 
 use super::WadHashtable;
 
 pub struct MountedWads {
     wad_trees: IndexMap<Uuid, WadTree>,
     wads: HashMap<Uuid, Wad<File>>,
+    edits: HashMap<Uuid, WadEdits>,
+    /// Parsed `.subchunktoc` of wads, `None` if the wad doesn't have one
+    subchunks: HashMap<Uuid, Option<Arc<WadSubchunks>>>,
 }
 
 impl MountedWads {
@@ -20,6 +31,8 @@ impl MountedWads {
         Self {
             wad_trees: IndexMap::default(),
             wads: HashMap::default(),
+            edits: HashMap::default(),
+            subchunks: HashMap::default(),
         }
     }
 
@@ -44,6 +57,39 @@ impl MountedWads {
 
         let _ = self.wad_trees.remove(&id);
         let _ = self.wads.remove(&id);
+        let _ = self.edits.remove(&id);
+        let _ = self.subchunks.remove(&id);
+    }
+
+    /// Rebuild the trees of all mounted wads against `hashtable`, keeping the state of items
+    /// which still exist
+    ///
+    /// If `path_hashes` is set, only wads which contain one of the chunks get rebuilt.
+    pub fn rebuild_wad_trees(
+        &mut self,
+        hashtable: &WadHashtable,
+        path_hashes: Option<&HashSet<u64>>,
+    ) -> Result<(), WadTreeError> {
+        info!("rebuilding wad trees (wad_count: {})", self.wad_trees.len());
+
+        for (id, wad_tree) in self.wad_trees.iter_mut() {
+            let Some(wad) = self.wads.get_mut(id) else {
+                continue;
+            };
+            if path_hashes.is_some_and(|path_hashes| {
+                !wad.chunks()
+                    .keys()
+                    .any(|path_hash| path_hashes.contains(path_hash))
+            }) {
+                continue;
+            }
+
+            let mut rebuilt_tree = WadTree::from_wad(wad, *id, wad_tree.wad_path(), hashtable)?;
+            rebuilt_tree.inherit_state(wad_tree);
+            *wad_tree = rebuilt_tree;
+        }
+
+        Ok(())
     }
 
     pub fn get_wad(&self, id: Uuid) -> Option<(&WadTree, &Wad<File>)> {
@@ -72,6 +118,42 @@ impl MountedWads {
     pub fn wads_mut(&mut self) -> &mut HashMap<Uuid, Wad<File>> {
         &mut self.wads
     }
+
+    pub fn get_edits(&self, id: Uuid) -> Option<&WadEdits> {
+        self.edits.get(&id)
+    }
+    pub fn edits_mut(&mut self, id: Uuid) -> &mut WadEdits {
+        self.edits.entry(id).or_default()
+    }
+    pub fn clear_edits(&mut self, id: Uuid) {
+        self.edits.remove(&id);
+    }
+    /// Get the subchunks of a wad, the `.subchunktoc` only gets read the first time
+    pub fn get_subchunks(&mut self, id: Uuid) -> eyre::Result<Option<Arc<WadSubchunks>>> {
+        if let Some(subchunks) = self.subchunks.get(&id) {
+            return Ok(subchunks.clone());
+        }
+
+        let (wad_tree, wad) = match (self.wad_trees.get(&id), self.wads.get_mut(&id)) {
+            (Some(wad_tree), Some(wad)) => (wad_tree, wad),
+            _ => return Err(eyre!("failed to find wad (wad_id: {})", id)),
+        };
+
+        // the subchunk fields of the TOC aren't exposed by league_toolkit so it has to be read again
+        let wad_path = wad_tree.wad_path();
+        let toc = WadToc::read(&mut File::open(wad_path)?)
+            .wrap_err(format!("failed to read wad toc: {}", wad_path))?;
+
+        let (mut decoder, chunks) = wad.decode();
+        let subchunks = WadSubchunks::load(&mut decoder, chunks, &toc, wad_path)?.map(Arc::new);
+
+        self.subchunks.insert(id, subchunks.clone());
+        Ok(subchunks)
+    }
+    pub fn get_wad_with_edits_mut(&mut self, id: Uuid) -> Option<(&mut Wad<File>, &WadEdits)> {
+        let wad = self.wads.get_mut(&id)?;
+        Some((wad, &*self.edits.entry(id).or_default()))
+    }
 }
 
 pub struct MountedWadsState(pub Mutex<MountedWads>);
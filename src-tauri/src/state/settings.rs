@@ -12,6 +12,8 @@ pub struct Settings {
     pub default_mount_directory: Option<String>,
     pub default_extraction_directory: Option<String>,
     pub league_directory: Option<String>,
+    /// Base url to download the wad hashtables from, uses CommunityDragon if not set
+    pub wad_hashtables_base_url: Option<String>,
 }
 
 impl Settings {
@@ -48,6 +50,7 @@ impl Default for Settings {
             default_mount_directory: None,
             default_extraction_directory: None,
             league_directory: None,
+            wad_hashtables_base_url: None,
         }
     }
 }
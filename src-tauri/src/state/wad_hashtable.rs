@@ -1,20 +1,36 @@
-use crate::api::hashtable::WadHashtableStatus;
-use color_eyre::eyre::{self, eyre, Result};
+use crate::api::hashtable::{WadHashtableFileStatus, WadHashtableStatus};
+use crate::core::hashes::{
+    HASHTABLE_CACHE_DIR, HASHTABLE_CACHE_EXTENSION, HASHTABLE_DOWNLOAD_DIR, HashtableCache,
+    open_hashtable_file, parse_hashtable,
+};
+use crate::paths::USER_WAD_HASHTABLE_FILE;
+use color_eyre::eyre::{self, Result, eyre};
 use parking_lot::Mutex;
 use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::Path,
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
     sync::Arc,
 };
 use tracing::info;
 use walkdir::WalkDir;
+use xxhash_rust::xxh3::xxh3_64;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Default)]
 pub struct WadHashtable {
     is_loaded: bool,
+    /// Paths added at runtime, e.g. discovered ones, which aren't in any loaded file yet
     items: HashMap<u64, Arc<str>>,
+    /// Caches of the hashtable files, later caches take priority
+    caches: Vec<HashtableCache>,
+    /// Paths assigned by the user, these take priority over everything else
+    overrides: HashMap<u64, Arc<str>>,
+    /// Whether the user hashtable was loaded (or doesn't exist yet), saving the overrides before
+    /// that would drop the ones stored in it
+    are_overrides_loaded: bool,
+    /// Diagnostics of every loaded file, in load order
+    files: Vec<WadHashtableFileStatus>,
 }
 
 impl WadHashtable {
@@ -22,62 +38,311 @@ impl WadHashtable {
         Ok(WadHashtable {
             is_loaded: false,
             items: HashMap::default(),
+            caches: Vec::default(),
+            overrides: HashMap::default(),
+            are_overrides_loaded: false,
+            files: Vec::default(),
         })
     }
 
     pub fn resolve_path(&self, path_hash: u64) -> Arc<str> {
-        self.items
-            .get(&path_hash)
-            .map(|x| x.clone())
-            .unwrap_or_else(|| format!("{:x}", path_hash).into())
+        self.get_path(path_hash)
+            .unwrap_or_else(|| format!("{:016x}", path_hash).into())
+    }
+
+    pub fn get_path(&self, path_hash: u64) -> Option<Arc<str>> {
+        if let Some(path) = self.overrides.get(&path_hash) {
+            return Some(path.clone());
+        }
+        if let Some(path) = self.items.get(&path_hash) {
+            return Some(path.clone());
+        }
+
+        self.caches
+            .iter()
+            .rev()
+            .find_map(|cache| cache.get(path_hash))
+            .map(Arc::from)
+    }
+
+    /// Add a single path, taking priority over the loaded hashtable files
+    pub fn insert(&mut self, path_hash: u64, path: impl Into<Arc<str>>) {
+        self.items.insert(path_hash, path.into());
+    }
+
+    pub fn clear(&mut self) {
+        self.is_loaded = false;
+        self.items.clear();
+        self.caches.clear();
+        self.overrides.clear();
+        self.are_overrides_loaded = false;
+        self.files.clear();
     }
 
+    pub fn overrides(&self) -> &HashMap<u64, Arc<str>> {
+        &self.overrides
+    }
+
+    pub fn are_overrides_loaded(&self) -> bool {
+        self.are_overrides_loaded
+    }
+
+    pub fn set_override(&mut self, path_hash: u64, path: impl Into<Arc<str>>) {
+        self.overrides.insert(path_hash, path.into());
+    }
+
+    pub fn remove_override(&mut self, path_hash: u64) -> Option<Arc<str>> {
+        self.overrides.remove(&path_hash)
+    }
+
+    /// Write the overrides to the user hashtable in `dir`, sorted by hash
+    ///
+    /// Fails if the user hashtable wasn't loaded, since it would be overwritten with only the
+    /// overrides which are in memory.
+    pub fn save_overrides(&self, dir: impl AsRef<Path>) -> eyre::Result<()> {
+        if !self.are_overrides_loaded {
+            return Err(eyre!("user wad hashtable isn't loaded"));
+        }
+
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let mut overrides = self.overrides.iter().collect::<Vec<_>>();
+        overrides.sort_by_key(|(path_hash, _)| **path_hash);
+
+        let mut writer = BufWriter::new(File::create(dir.join(USER_WAD_HASHTABLE_FILE))?);
+        for (path_hash, path) in overrides {
+            writeln!(writer, "{:016x} {}", path_hash, path)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn load_overrides(&mut self, path: &Path) -> eyre::Result<WadHashtableFileStatus> {
+        // only apply the file if all of it could be read
+        let mut overrides = HashMap::new();
+        let stats = parse_hashtable(open_hashtable_file(path)?, |hash, path| {
+            overrides.insert(hash, path.into());
+        })?;
+        self.overrides.extend(overrides);
+        self.are_overrides_loaded = true;
+
+        Ok(WadHashtableFileStatus {
+            entry_count: stats.entry_count,
+            invalid_line_count: stats.invalid_line_count,
+            ..Default::default()
+        })
+    }
+
+    /// Load every hashtable file in `dir`, through a binary cache stored in
+    /// `<dir>/.cache` which gets rebuilt whenever the size or mtime of a file changes
+    ///
+    /// The user hashtable is loaded last, as overrides. Files which fail to load are skipped
+    /// and reported through [`WadHashtable::status`].
     pub fn add_from_dir(&mut self, dir: impl AsRef<Path>) -> eyre::Result<()> {
-        info!("loading wad hasthables from dir: {:?}", dir.as_ref());
+        let dir = dir.as_ref();
+        info!("loading wad hasthables from dir: {:?}", dir);
 
-        for wad_hashtable_entry in WalkDir::new(dir).into_iter().filter_map(|x| x.ok()) {
-            if !wad_hashtable_entry.file_type().is_file() {
+        let cache_dir = dir.join(HASHTABLE_CACHE_DIR);
+        let user_hashtable_path = dir.join(USER_WAD_HASHTABLE_FILE);
+        let mut cache_paths = HashSet::new();
+
+        for wad_hashtable_entry in WalkDir::new(dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.file_name() != HASHTABLE_CACHE_DIR
+                    && entry.file_name() != HASHTABLE_DOWNLOAD_DIR
+            })
+            .filter_map(|x| x.ok())
+        {
+            if !wad_hashtable_entry.file_type().is_file()
+                || wad_hashtable_entry.path() == user_hashtable_path
+            {
                 continue;
             }
 
-            info!("loading wad hasthable: {:?}", wad_hashtable_entry.path());
-            self.add_from_file(&mut File::open(wad_hashtable_entry.path())?)?;
+            let source_path = wad_hashtable_entry.path();
+            let cache_path = hashtable_cache_path(dir, &cache_dir, source_path);
+
+            info!("loading wad hasthable: {:?}", source_path);
+            // building the cache already falls back to memory if the cache can't be written,
+            // so an error here means the file itself is unreadable
+            let file_status =
+                HashtableCache::load_or_build(source_path, &cache_path).map(|cache| {
+                    info!("loaded cache (entry_count: {})", cache.entry_count());
+                    self.push_cache(cache)
+                });
+
+            self.push_file_status(dir, source_path, file_status);
+            cache_paths.insert(cache_path);
+        }
+
+        remove_stale_caches(&cache_dir, &cache_paths);
+
+        if user_hashtable_path.is_file() {
+            info!("loading user wad hashtable: {:?}", user_hashtable_path);
+            let file_status = self.load_overrides(&user_hashtable_path);
+            self.push_file_status(dir, &user_hashtable_path, file_status);
+        } else {
+            self.are_overrides_loaded = true;
         }
+
         info!("loaded");
         self.is_loaded = true;
 
         Ok(())
     }
 
-    pub fn add_from_file(&mut self, file: &mut File) -> eyre::Result<()> {
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
+    /// Parse a hashtable file into memory, bypassing the cache file
+    pub fn add_from_file(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> eyre::Result<WadHashtableFileStatus> {
+        Ok(self.push_cache(HashtableCache::from_source(path)?))
+    }
 
-        while let Some(Ok(line)) = lines.next() {
-            let mut components = line.split(' ');
+    fn push_cache(&mut self, cache: HashtableCache) -> WadHashtableFileStatus {
+        let file_status = WadHashtableFileStatus {
+            entry_count: cache.entry_count(),
+            invalid_line_count: cache.invalid_line_count(),
+            conflict_count: cache.conflict_count(),
+            ..Default::default()
+        };
+        self.caches.push(cache);
 
-            let hash = components.next().ok_or(eyre!("failed to read hash"))?;
-            let hash = u64::from_str_radix(hash, 16).expect("failed to convert hash");
-            let path = itertools::join(components, " ");
+        file_status
+    }
 
-            self.items.insert(hash, path.into());
-        }
+    fn push_file_status(
+        &mut self,
+        dir: &Path,
+        path: &Path,
+        file_status: eyre::Result<WadHashtableFileStatus>,
+    ) {
+        let name = path
+            .strip_prefix(dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .into_owned();
 
-        Ok(())
-    }
+        let file_status = match file_status {
+            Ok(file_status) => file_status,
+            Err(error) => {
+                tracing::error!("failed to load wad hashtable: {:?} ({:?})", path, error);
+                WadHashtableFileStatus {
+                    error: Some(error.to_string()),
+                    ..Default::default()
+                }
+            }
+        };
 
-    pub fn items(&self) -> &HashMap<u64, Arc<str>> {
-        &self.items
-    }
-    pub fn items_mut(&mut self) -> &mut HashMap<u64, Arc<str>> {
-        &mut self.items
+        self.files.push(WadHashtableFileStatus {
+            name,
+            ..file_status
+        });
     }
 
     pub fn status(&self) -> WadHashtableStatus {
         WadHashtableStatus {
             is_loaded: self.is_loaded,
+            entry_count: self.files.iter().map(|file| file.entry_count).sum(),
+            invalid_line_count: self.files.iter().map(|file| file.invalid_line_count).sum(),
+            conflict_count: self.files.iter().map(|file| file.conflict_count).sum(),
+            files: self.files.clone(),
+        }
+    }
+}
+
+/// Caches are named after the hash of the source path, relative to the hashtables directory
+fn hashtable_cache_path(dir: &Path, cache_dir: &Path, source_path: &Path) -> PathBuf {
+    let relative_path = source_path.strip_prefix(dir).unwrap_or(source_path);
+
+    cache_dir.join(format!(
+        "{:016x}.{}",
+        xxh3_64(relative_path.to_string_lossy().as_bytes()),
+        HASHTABLE_CACHE_EXTENSION
+    ))
+}
+
+fn remove_stale_caches(cache_dir: &Path, cache_paths: &HashSet<PathBuf>) {
+    let Ok(entries) = fs::read_dir(cache_dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|x| x.ok()) {
+        let path = entry.path();
+        if !cache_paths.contains(&path) {
+            info!("removing stale hashtable cache: {:?}", path);
+            let _ = fs::remove_file(path);
         }
     }
 }
 
 pub struct WadHashtableState(pub Mutex<WadHashtable>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("wad_hashtable_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn unloaded_overrides_do_not_truncate_user_hashtable() {
+        let dir = temp_dir();
+        let user_hashtable_path = dir.join(USER_WAD_HASHTABLE_FILE);
+        let content = "0000000000000001 saved/path.bin\n";
+        fs::write(&user_hashtable_path, content).unwrap();
+
+        let mut wad_hashtable = WadHashtable::default();
+        wad_hashtable.set_override(2, "new/path.bin");
+
+        assert!(wad_hashtable.save_overrides(&dir).is_err());
+        assert_eq!(fs::read_to_string(&user_hashtable_path).unwrap(), content);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn saves_loaded_overrides() {
+        let dir = temp_dir();
+        let user_hashtable_path = dir.join(USER_WAD_HASHTABLE_FILE);
+        fs::write(&user_hashtable_path, "0000000000000001 saved/path.bin\n").unwrap();
+
+        let mut wad_hashtable = WadHashtable::default();
+        wad_hashtable.add_from_dir(&dir).unwrap();
+        wad_hashtable.set_override(2, "new/path.bin");
+        wad_hashtable.save_overrides(&dir).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&user_hashtable_path).unwrap(),
+            "0000000000000001 saved/path.bin\n0000000000000002 new/path.bin\n"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn saves_overrides_without_user_hashtable() {
+        let dir = temp_dir();
+
+        let mut wad_hashtable = WadHashtable::default();
+        wad_hashtable.add_from_dir(&dir).unwrap();
+        wad_hashtable.set_override(1, "new/path.bin");
+        wad_hashtable.save_overrides(&dir).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join(USER_WAD_HASHTABLE_FILE)).unwrap(),
+            "0000000000000001 new/path.bin\n"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,19 +1,45 @@
 use std::{cmp::min, fs::File, io::Write, path::Path};
 
-use color_eyre::eyre::{self, eyre};
+use color_eyre::eyre;
 use futures_util::StreamExt;
-use reqwest::IntoUrl;
+use reqwest::{IntoUrl, StatusCode, header};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadStatus {
+    Downloaded {
+        etag: Option<String>,
+    },
+    /// The remote file still matches the ETag that was sent with the request
+    NotModified,
+    NotFound,
+}
 
 pub async fn download_file(
+    client: &reqwest::Client,
     url: impl IntoUrl,
     path: impl AsRef<Path>,
-    mut on_progress: impl FnMut(usize, usize),
-) -> eyre::Result<()> {
-    let response = reqwest::get(url).await?;
+    etag: Option<&str>,
+    mut on_progress: impl FnMut(usize, Option<usize>),
+) -> eyre::Result<DownloadStatus> {
+    let mut request = client.get(url);
+    if let Some(etag) = etag {
+        request = request.header(header::IF_NONE_MATCH, etag);
+    }
+
+    let response = request.send().await?;
+    match response.status() {
+        StatusCode::NOT_MODIFIED => return Ok(DownloadStatus::NotModified),
+        StatusCode::NOT_FOUND => return Ok(DownloadStatus::NotFound),
+        _ => {}
+    }
+    let response = response.error_for_status()?;
 
-    let total_size = response
-        .content_length()
-        .ok_or(eyre!("failed to get content length"))? as usize;
+    let etag = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(|etag| etag.to_string());
+    let total_size = response.content_length().map(|size| size as usize);
 
     let mut file = File::create(path)?;
     let mut response_stream = response.bytes_stream();
@@ -24,9 +50,12 @@ pub async fn download_file(
 
         file.write_all(&chunk)?;
 
-        downloaded_bytes = min(downloaded_bytes + (chunk.len()), total_size);
+        downloaded_bytes += chunk.len();
+        if let Some(total_size) = total_size {
+            downloaded_bytes = min(downloaded_bytes, total_size);
+        }
         on_progress(downloaded_bytes, total_size);
     }
 
-    Ok(())
+    Ok(DownloadStatus::Downloaded { etag })
 }
@@ -0,0 +1,39 @@
+import { useMutation } from "@tanstack/react-query";
+import { core } from "@tauri-apps/api";
+import { queryClient } from "../../../lib/query";
+import { gameExplorerQueryKeys } from "../../game-explorer/queryKeys";
+import { wadQueryKeys } from "../../wad/queryKeys";
+import { wadHashtableCommands } from "../commands";
+import { wadHashtableQueryKeys } from "../queryKeys";
+
+export type UseAddWadHashtableOverrideContext = {
+  pathHash: string;
+  path: string;
+};
+
+export const addWadHashtableOverride = ({
+  pathHash,
+  path,
+}: UseAddWadHashtableOverrideContext) =>
+  core.invoke(wadHashtableCommands.addWadHashtableOverride, {
+    pathHash,
+    path,
+  });
+
+export const useAddWadHashtableOverride = () => {
+  return useMutation({
+    mutationFn: addWadHashtableOverride,
+    onSuccess: () => {
+      queryClient.invalidateQueries({
+        queryKey: wadHashtableQueryKeys.wadHashtableOverrides,
+      });
+
+      // items of mounted wads get renamed
+      queryClient.invalidateQueries({ queryKey: wadQueryKeys.mountedWads });
+      queryClient.invalidateQueries({ queryKey: wadQueryKeys.wads });
+      queryClient.invalidateQueries({
+        queryKey: gameExplorerQueryKeys.items(),
+      });
+    },
+  });
+};
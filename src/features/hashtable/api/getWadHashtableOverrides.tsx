@@ -0,0 +1,18 @@
+import { useQuery } from "@tanstack/react-query";
+import { core } from "@tauri-apps/api";
+
+import type { WadHashtableOverride } from "..";
+import { wadHashtableCommands } from "../commands";
+import { wadHashtableQueryKeys } from "../queryKeys";
+
+export const getWadHashtableOverrides = () =>
+  core.invoke<WadHashtableOverride[]>(
+    wadHashtableCommands.getWadHashtableOverrides,
+  );
+
+export const useWadHashtableOverrides = () => {
+  return useQuery({
+    queryKey: wadHashtableQueryKeys.wadHashtableOverrides,
+    queryFn: getWadHashtableOverrides,
+  });
+};
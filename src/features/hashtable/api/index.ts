@@ -1,2 +1,6 @@
+export * from "./addWadHashtableOverride";
+export * from "./getWadHashtableOverrides";
 export * from "./getWadHashtableStatus";
+export * from "./loadBinHashtables";
 export * from "./loadWadHashtables";
+export * from "./removeWadHashtableOverride";
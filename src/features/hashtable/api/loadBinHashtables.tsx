@@ -0,0 +1,38 @@
+import { useMutation } from "@tanstack/react-query";
+import { core } from "@tauri-apps/api";
+import { queryClient } from "../../../lib/query";
+import { apiErrorSchema } from "../../../types/error";
+import { wadQueryKeys } from "../../wad/queryKeys";
+import { wadHashtableCommands } from "../commands";
+import type { BinHashtableStatus } from "../types";
+
+/** Resolves to `null` without bin hashtables, bin names stay hashed then */
+export const loadBinHashtables = async () => {
+  try {
+    return await core.invoke<BinHashtableStatus>(
+      wadHashtableCommands.loadBinHashtables,
+    );
+  } catch (error) {
+    const apiError = apiErrorSchema.safeParse(error);
+    if (
+      apiError.success &&
+      apiError.data.extensions?.some(
+        (extension) => extension.kind === "binHashtablesMissing",
+      )
+    ) {
+      return null;
+    }
+
+    throw error;
+  }
+};
+
+export const useLoadBinHashtables = () => {
+  return useMutation({
+    mutationFn: loadBinHashtables,
+    onSuccess: () => {
+      // bin previews resolve their names when they are loaded
+      queryClient.invalidateQueries({ queryKey: wadQueryKeys.wads });
+    },
+  });
+};
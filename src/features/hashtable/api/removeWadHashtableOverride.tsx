@@ -0,0 +1,36 @@
+import { useMutation } from "@tanstack/react-query";
+import { core } from "@tauri-apps/api";
+import { queryClient } from "../../../lib/query";
+import { gameExplorerQueryKeys } from "../../game-explorer/queryKeys";
+import { wadQueryKeys } from "../../wad/queryKeys";
+import { wadHashtableCommands } from "../commands";
+import { wadHashtableQueryKeys } from "../queryKeys";
+
+export type UseRemoveWadHashtableOverrideContext = {
+  pathHash: string;
+};
+
+export const removeWadHashtableOverride = ({
+  pathHash,
+}: UseRemoveWadHashtableOverrideContext) =>
+  core.invoke(wadHashtableCommands.removeWadHashtableOverride, {
+    pathHash,
+  });
+
+export const useRemoveWadHashtableOverride = () => {
+  return useMutation({
+    mutationFn: removeWadHashtableOverride,
+    onSuccess: () => {
+      queryClient.invalidateQueries({
+        queryKey: wadHashtableQueryKeys.wadHashtableOverrides,
+      });
+
+      // items of mounted wads get renamed
+      queryClient.invalidateQueries({ queryKey: wadQueryKeys.mountedWads });
+      queryClient.invalidateQueries({ queryKey: wadQueryKeys.wads });
+      queryClient.invalidateQueries({
+        queryKey: gameExplorerQueryKeys.items(),
+      });
+    },
+  });
+};
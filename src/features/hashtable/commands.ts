@@ -1,4 +1,8 @@
 export const wadHashtableCommands = {
+  addWadHashtableOverride: "add_wad_hashtable_override" as const,
+  getWadHashtableOverrides: "get_wad_hashtable_overrides" as const,
   getWadHashtableStatus: "get_wad_hashtable_status" as const,
+  loadBinHashtables: "load_bin_hashtables" as const,
   loadWadHashtables: "load_wad_hashtables" as const,
+  removeWadHashtableOverride: "remove_wad_hashtable_override" as const,
 };
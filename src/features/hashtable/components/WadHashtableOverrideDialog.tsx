@@ -0,0 +1,85 @@
+import * as RadixDialog from "@radix-ui/react-dialog";
+import type React from "react";
+import { useCallback, useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+
+import { Button, TextField } from "../../../components";
+import { useAddWadHashtableOverride } from "../api";
+
+export type WadHashtableOverrideDialogProps = {
+  open: boolean;
+  onOpenChange: (open: boolean) => void;
+
+  pathHash: string;
+  defaultPath?: string;
+};
+
+/**
+ * Assigns a path to a hash, the path takes priority over every other hashtable
+ */
+export const WadHashtableOverrideDialog: React.FC<
+  WadHashtableOverrideDialogProps
+> = ({ open, onOpenChange, pathHash, defaultPath = "" }) => {
+  const [t] = useTranslation("hashtable");
+
+  const [path, setPath] = useState(defaultPath);
+  const addOverride = useAddWadHashtableOverride();
+
+  useEffect(() => {
+    if (open) {
+      setPath(defaultPath);
+    }
+  }, [open, defaultPath]);
+
+  const handleSubmit = useCallback(
+    (e: React.FormEvent) => {
+      e.preventDefault();
+
+      addOverride.mutate(
+        { pathHash, path },
+        { onSuccess: () => onOpenChange(false) },
+      );
+    },
+    [addOverride, onOpenChange, path, pathHash],
+  );
+
+  return (
+    <RadixDialog.Root open={open} onOpenChange={onOpenChange}>
+      <RadixDialog.Portal>
+        <RadixDialog.Overlay className="fixed inset-0 animate-fadeIn bg-gray-900/40 transition-opacity" />
+        <RadixDialog.Content className="fixed left-[50%] top-[50%] w-[60vw] translate-x-[-50%] translate-y-[-50%] transform rounded border border-gray-600 bg-gray-800 p-4 shadow-xl">
+          <RadixDialog.Title className="pb-4 text-xl text-gray-50">
+            {t("overrideDialog.title")}
+          </RadixDialog.Title>
+          <form className="flex flex-col gap-4" onSubmit={handleSubmit}>
+            <TextField
+              label={t("overrideDialog.pathHash")}
+              value={pathHash}
+              readOnly
+            />
+            <TextField
+              label={t("overrideDialog.path")}
+              value={path}
+              onChange={(e) => setPath(e.target.value)}
+              autoFocus
+            />
+            <div className="flex flex-row justify-end gap-2">
+              <RadixDialog.Close asChild>
+                <Button type="button" variant="ghost">
+                  {t("overrideDialog.cancel")}
+                </Button>
+              </RadixDialog.Close>
+              <Button
+                type="submit"
+                variant="filled"
+                disabled={!path.trim() || addOverride.isPending}
+              >
+                {t("overrideDialog.submit")}
+              </Button>
+            </div>
+          </form>
+        </RadixDialog.Content>
+      </RadixDialog.Portal>
+    </RadixDialog.Root>
+  );
+};
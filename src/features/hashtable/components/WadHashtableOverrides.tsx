@@ -0,0 +1,52 @@
+import { useTranslation } from "react-i18next";
+import { VscTrash } from "react-icons/vsc";
+
+import { ActionIcon, Spinner } from "../../../components";
+import {
+  useRemoveWadHashtableOverride,
+  useWadHashtableOverrides,
+} from "../api";
+
+export const WadHashtableOverrides = () => {
+  const [t] = useTranslation("hashtable");
+
+  const overrides = useWadHashtableOverrides();
+  const removeOverride = useRemoveWadHashtableOverride();
+
+  return (
+    <div className="flex flex-col gap-2">
+      <h2 className="text-xl text-gray-50">{t("overrides.title")}</h2>
+      {overrides.isLoading && <Spinner />}
+      {overrides.isSuccess && overrides.data.length === 0 && (
+        <p className="text-sm text-gray-400">{t("overrides.empty")}</p>
+      )}
+      {overrides.isSuccess && overrides.data.length > 0 && (
+        <ul className="flex flex-col gap-1">
+          {overrides.data.map((override) => (
+            <li
+              key={override.pathHash}
+              className="flex flex-row items-center gap-4 rounded bg-gray-800 px-2 py-1"
+            >
+              <span className="font-mono text-sm text-gray-400">
+                {override.pathHash}
+              </span>
+              <span className="grow truncate text-gray-50">
+                {override.path}
+              </span>
+              <ActionIcon
+                size="sm"
+                variant="ghost"
+                icon={VscTrash}
+                title={t("overrides.remove")}
+                disabled={removeOverride.isPending}
+                onClick={() =>
+                  removeOverride.mutate({ pathHash: override.pathHash })
+                }
+              />
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+};
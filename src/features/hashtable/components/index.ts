@@ -0,0 +1,2 @@
+export * from "./WadHashtableOverrideDialog";
+export * from "./WadHashtableOverrides";
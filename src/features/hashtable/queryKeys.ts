@@ -1,3 +1,4 @@
 export const wadHashtableQueryKeys = {
   wadHashtableStatus: ["wad_hashtable", "status"] as const,
+  wadHashtableOverrides: ["wad_hashtable", "overrides"] as const,
 };
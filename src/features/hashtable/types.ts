@@ -1,3 +1,16 @@
 export type WadHashtableStatus = {
   isLoaded: boolean;
 };
+
+export type BinHashtableStatus = {
+  isLoaded: boolean;
+  entryCount: number;
+  fieldCount: number;
+  typeCount: number;
+  hashCount: number;
+};
+
+export type WadHashtableOverride = {
+  pathHash: string;
+  path: string;
+};
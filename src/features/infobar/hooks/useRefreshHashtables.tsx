@@ -9,7 +9,10 @@ import {
   getApiErrorExtension,
   wadHashtablesMissingExtensionSchema,
 } from "../../../types/error";
-import { useLoadWadHashtables } from "../../hashtable";
+import {
+  useLoadBinHashtables,
+  useLoadWadHashtables,
+} from "../../hashtable";
 
 export const useRefreshHashtables = () => {
   const [t] = useTranslation("common");
@@ -19,12 +22,14 @@ export const useRefreshHashtables = () => {
   const [actionId] = useState(uuidv4());
 
   const loadHashtablesMutation = useLoadWadHashtables();
+  const { mutate: loadBinHashtablesMutate } = useLoadBinHashtables();
 
   const handleRefresh = useCallback(() => {
     hashtablesLoadingToastId.current = toast.info("Loading hashtables...", {
       autoClose: false,
     });
 
+    loadBinHashtablesMutate();
     loadHashtablesMutation.mutate(
       { actionId },
       {
@@ -69,7 +74,7 @@ export const useRefreshHashtables = () => {
         },
       },
     );
-  }, [actionId, loadHashtablesMutation, t]);
+  }, [actionId, loadBinHashtablesMutate, loadHashtablesMutation, t]);
 
   return { handleRefresh, loadHashtablesMutation };
 };
@@ -67,6 +67,13 @@ export const SettingsForm: React.FC<SettingsFormProps> = ({
             name="leagueDirectory"
             label={t("leagueDirectory.label")}
           />
+          <Form.TextField
+            control={control}
+            name="wadHashtablesBaseUrl"
+            className="min-w-[500px]"
+            label={t("wadHashtablesBaseUrl.label")}
+            placeholder={t("wadHashtablesBaseUrl.placeholder")}
+          />
         </div>
         <DevTool control={control} />
       </form>
@@ -6,6 +6,7 @@ export const createSettigsFormData = (settings: Settings): SettingsFormData => {
     defaultExtractionDirectory: settings.defaultExtractionDirectory,
     defaultMountDirectory: settings.defaultMountDirectory,
     leagueDirectory: settings.leagueDirectory,
+    wadHashtablesBaseUrl: settings.wadHashtablesBaseUrl ?? "",
   };
 };
 
@@ -17,5 +18,6 @@ export const createSettingsFromFormData = (
     defaultExtractionDirectory: data.defaultExtractionDirectory,
     defaultMountDirectory: data.defaultMountDirectory,
     leagueDirectory: data.leagueDirectory,
+    wadHashtablesBaseUrl: data.wadHashtablesBaseUrl || null,
   };
 };
@@ -1,12 +1,14 @@
 import { z } from "zod";
 
-import { pathStringSchema } from "../../utils";
+import { pathStringSchema, urlStringSchema } from "../../utils";
 
 export type Settings = {
   openDirectoryAfterExtraction: boolean;
   defaultMountDirectory: string | null;
   defaultExtractionDirectory: string | null;
   leagueDirectory: string | null;
+  /** Base url to download the wad hashtables from, CommunityDragon if not set */
+  wadHashtablesBaseUrl: string | null;
 };
 
 export type SettingsFormData = z.infer<typeof settingsFormDataSchema>;
@@ -15,4 +17,6 @@ export const settingsFormDataSchema = z.object({
   defaultMountDirectory: pathStringSchema.nullable(),
   defaultExtractionDirectory: pathStringSchema.nullable(),
   leagueDirectory: pathStringSchema.nullable(),
+  // an empty input means the default source
+  wadHashtablesBaseUrl: urlStringSchema.or(z.literal("")),
 });
@@ -0,0 +1,24 @@
+import { useQuery } from "@tanstack/react-query";
+import { core } from "@tauri-apps/api";
+import { wadPreviewCommands } from "../commands";
+import { wadPreviewQueryKeys } from "../queryKeys";
+import type { BinPreviewResponse } from "../types";
+
+export const getBinPreview = (wadId: string, itemId: string) =>
+  core.invoke<BinPreviewResponse>(wadPreviewCommands.getBinPreview, {
+    wadId,
+    itemId,
+  });
+
+export const useBinPreview = ({
+  wadId,
+  itemId,
+}: {
+  wadId: string;
+  itemId: string;
+}) => {
+  return useQuery({
+    queryKey: wadPreviewQueryKeys.binPreview(wadId, itemId),
+    queryFn: () => getBinPreview(wadId, itemId),
+  });
+};
@@ -0,0 +1,42 @@
+import { keepPreviousData, useQuery } from "@tanstack/react-query";
+import { core } from "@tauri-apps/api";
+import { wadPreviewCommands } from "../commands";
+import { wadPreviewQueryKeys } from "../queryKeys";
+import type { ChunkBytesRangeResponse } from "../types";
+
+export const getChunkBytesRange = (
+  wadId: string,
+  itemId: string,
+  offset: number,
+  length: number,
+) =>
+  core.invoke<ChunkBytesRangeResponse>(wadPreviewCommands.getChunkBytesRange, {
+    wadId,
+    itemId,
+    offset,
+    length,
+  });
+
+export const useChunkBytesRange = ({
+  wadId,
+  itemId,
+  offset,
+  length,
+}: {
+  wadId: string;
+  itemId: string;
+  offset: number;
+  length: number;
+}) => {
+  return useQuery({
+    queryKey: wadPreviewQueryKeys.chunkBytesRange(
+      wadId,
+      itemId,
+      offset,
+      length,
+    ),
+    queryFn: () => getChunkBytesRange(wadId, itemId, offset, length),
+    // keep showing the current page while the next one loads
+    placeholderData: keepPreviousData,
+  });
+};
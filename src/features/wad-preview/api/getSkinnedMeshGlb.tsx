@@ -0,0 +1,30 @@
+import { useQuery } from "@tanstack/react-query";
+import { core } from "@tauri-apps/api";
+import { wadPreviewCommands } from "../commands";
+import { wadPreviewQueryKeys } from "../queryKeys";
+
+export const getSkinnedMeshGlb = async (
+  wadId: string,
+  itemId: string,
+  skeletonItemId?: string,
+) => {
+  const result = await core.invoke<number[]>(
+    wadPreviewCommands.getSkinnedMeshGlb,
+    { wadId, itemId, skeletonItemId },
+  );
+
+  return new Uint8Array(result);
+};
+
+export const useSkinnedMeshGlb = ({
+  wadId,
+  itemId,
+}: {
+  wadId: string;
+  itemId: string;
+}) => {
+  return useQuery({
+    queryKey: wadPreviewQueryKeys.skinnedMeshGlb(wadId, itemId),
+    queryFn: () => getSkinnedMeshGlb(wadId, itemId),
+  });
+};
@@ -0,0 +1,24 @@
+import { useQuery } from "@tanstack/react-query";
+import { core } from "@tauri-apps/api";
+import { wadPreviewCommands } from "../commands";
+import { wadPreviewQueryKeys } from "../queryKeys";
+import type { TextPreviewResponse } from "../types";
+
+export const getTextPreview = (wadId: string, itemId: string) =>
+  core.invoke<TextPreviewResponse>(wadPreviewCommands.getTextPreview, {
+    wadId,
+    itemId,
+  });
+
+export const useTextPreview = ({
+  wadId,
+  itemId,
+}: {
+  wadId: string;
+  itemId: string;
+}) => {
+  return useQuery({
+    queryKey: wadPreviewQueryKeys.textPreview(wadId, itemId),
+    queryFn: () => getTextPreview(wadId, itemId),
+  });
+};
@@ -1 +1,5 @@
+export * from "./getBinPreview";
+export * from "./getChunkBytesRange";
 export * from "./getImageBytes";
+export * from "./getSkinnedMeshGlb";
+export * from "./getTextPreview";
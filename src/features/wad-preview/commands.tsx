@@ -1,3 +1,7 @@
 export const wadPreviewCommands = {
+  getBinPreview: "get_bin_preview",
+  getChunkBytesRange: "get_chunk_bytes_range",
   getImageBytes: "get_image_bytes",
+  getSkinnedMeshGlb: "get_skinned_mesh_glb",
+  getTextPreview: "get_text_preview",
 } as const;
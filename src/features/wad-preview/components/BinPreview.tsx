@@ -0,0 +1,168 @@
+import type React from "react";
+import { match } from "ts-pattern";
+
+import { Spinner } from "../../../components";
+import { useBinPreview } from "../api";
+import type {
+  BinPreviewEntry,
+  BinPreviewField,
+  BinPreviewValue,
+} from "../types";
+
+export type BinPreviewProps = {
+  wadId: string;
+  itemId: string;
+};
+
+export const BinPreview: React.FC<BinPreviewProps> = ({ wadId, itemId }) => {
+  const binPreview = useBinPreview({ wadId, itemId });
+
+  if (binPreview.isLoading) {
+    return <Spinner />;
+  }
+
+  if (!binPreview.isSuccess) {
+    return null;
+  }
+
+  return (
+    <div className="h-full overflow-auto p-2 font-mono text-sm text-gray-50">
+      <p className="text-gray-400">
+        {binPreview.data.isOverride ? "PTCH" : "PROP"} v
+        {binPreview.data.version}
+      </p>
+      {binPreview.data.dependencies.length > 0 && (
+        <div className="py-1">
+          {binPreview.data.dependencies.map((dependency) => (
+            <p key={dependency} className="text-green-400">
+              "{dependency}"
+            </p>
+          ))}
+        </div>
+      )}
+      {binPreview.data.entries.map((entry) => (
+        <BinEntry key={entry.path} entry={entry} />
+      ))}
+    </div>
+  );
+};
+
+const BinEntry = ({ entry }: { entry: BinPreviewEntry }) => {
+  return (
+    <div className="py-1">
+      <span className="text-yellow-300">{entry.path}</span>
+      {" = "}
+      <BinFields className={entry.className} fields={entry.fields} />
+    </div>
+  );
+};
+
+const BinFields = ({
+  className,
+  fields,
+}: {
+  className: string;
+  fields: BinPreviewField[];
+}) => {
+  return (
+    <>
+      <span className="text-blue-400">{className}</span>
+      {" {"}
+      <div className="pl-4">
+        {fields.map((field, index) => (
+          // biome-ignore lint/suspicious/noArrayIndexKey: fields are never reordered
+          <div key={index}>
+            <span className="text-teal-400">{field.name}</span>
+            {": "}
+            <BinValue value={field.value} />
+          </div>
+        ))}
+      </div>
+      {"}"}
+    </>
+  );
+};
+
+const BinValue = ({ value }: { value: BinPreviewValue }): React.ReactNode => {
+  return match(value)
+    .with({ kind: "none" }, () => <span className="text-gray-400">null</span>)
+    .with({ kind: "bool" }, { kind: "bitBool" }, ({ value }) => (
+      <span className="text-purple-400">{value.toString()}</span>
+    ))
+    .with(
+      { kind: "string" },
+      { kind: "wadChunkLink" },
+      { kind: "objectLink" },
+      ({ value }) => <span className="text-green-400">"{value}"</span>,
+    )
+    .with({ kind: "hash" }, ({ value }) => (
+      <span className="text-yellow-300">{value}</span>
+    ))
+    .with(
+      { kind: "vector2" },
+      { kind: "vector3" },
+      { kind: "vector4" },
+      { kind: "matrix44" },
+      { kind: "color" },
+      ({ value }) => (
+        <span className="text-orange-300">{`{ ${value.join(", ")} }`}</span>
+      ),
+    )
+    .with(
+      { kind: "container" },
+      { kind: "unorderedContainer" },
+      ({ value }) => (
+        <>
+          {"["}
+          <div className="pl-4">
+            {value.map((item, index) => (
+              // biome-ignore lint/suspicious/noArrayIndexKey: items are never reordered
+              <div key={index}>
+                <BinValue value={item} />
+              </div>
+            ))}
+          </div>
+          {"]"}
+        </>
+      ),
+    )
+    .with({ kind: "struct" }, { kind: "embedded" }, ({ value }) => (
+      <BinFields className={value.className} fields={value.fields} />
+    ))
+    .with({ kind: "optional" }, ({ value }) =>
+      value ? (
+        <BinValue value={value} />
+      ) : (
+        <span className="text-gray-400">null</span>
+      ),
+    )
+    .with({ kind: "map" }, ({ value }) => (
+      <>
+        {"{"}
+        <div className="pl-4">
+          {value.map((entry, index) => (
+            // biome-ignore lint/suspicious/noArrayIndexKey: entries are never reordered
+            <div key={index}>
+              <BinValue value={entry.key} />
+              {" = "}
+              <BinValue value={entry.value} />
+            </div>
+          ))}
+        </div>
+        {"}"}
+      </>
+    ))
+    .with(
+      { kind: "i8" },
+      { kind: "u8" },
+      { kind: "i16" },
+      { kind: "u16" },
+      { kind: "i32" },
+      { kind: "u32" },
+      { kind: "i64" },
+      { kind: "u64" },
+      { kind: "f32" },
+      ({ value }) => <span className="text-orange-300">{value}</span>,
+    )
+    .exhaustive();
+};
@@ -0,0 +1,96 @@
+import type React from "react";
+import { useEffect, useState } from "react";
+import { VscChevronLeft, VscChevronRight } from "react-icons/vsc";
+
+import { ActionIcon, Spinner } from "../../../components";
+import { useChunkBytesRange } from "../api";
+
+const BYTES_PER_ROW = 16;
+const PAGE_SIZE = BYTES_PER_ROW * 256;
+
+export type HexPreviewProps = {
+  wadId: string;
+  itemId: string;
+};
+
+export const HexPreview: React.FC<HexPreviewProps> = ({ wadId, itemId }) => {
+  const [offset, setOffset] = useState(0);
+
+  useEffect(() => {
+    setOffset(0);
+  }, [wadId, itemId]);
+
+  const bytesRange = useChunkBytesRange({
+    wadId,
+    itemId,
+    offset,
+    length: PAGE_SIZE,
+  });
+
+  if (bytesRange.isLoading) {
+    return <Spinner />;
+  }
+
+  if (!bytesRange.isSuccess) {
+    return null;
+  }
+
+  const { bytes, totalSize } = bytesRange.data;
+  const rows = [];
+  for (let i = 0; i < bytes.length; i += BYTES_PER_ROW) {
+    rows.push(bytes.slice(i, i + BYTES_PER_ROW));
+  }
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="flex flex-row items-center gap-2 border-b border-gray-600 p-1 text-sm text-gray-400">
+        <ActionIcon
+          size="sm"
+          variant="ghost"
+          icon={VscChevronLeft}
+          disabled={offset === 0}
+          onClick={() => setOffset(Math.max(0, offset - PAGE_SIZE))}
+        />
+        <ActionIcon
+          size="sm"
+          variant="ghost"
+          icon={VscChevronRight}
+          disabled={offset + PAGE_SIZE >= totalSize}
+          onClick={() => setOffset(offset + PAGE_SIZE)}
+        />
+        <span>
+          {formatOffset(offset)} - {formatOffset(offset + bytes.length)} /{" "}
+          {formatOffset(totalSize)}
+        </span>
+      </div>
+      <div className="overflow-auto p-2 font-mono text-sm text-gray-50">
+        {rows.map((row, index) => {
+          const rowOffset = offset + index * BYTES_PER_ROW;
+
+          return (
+            <div
+              key={rowOffset}
+              className="flex flex-row gap-4 whitespace-pre"
+            >
+              <span className="text-gray-400">{formatOffset(rowOffset)}</span>
+              <span>
+                {row
+                  .map((byte) => byte.toString(16).padStart(2, "0"))
+                  .join(" ")
+                  .padEnd(BYTES_PER_ROW * 3 - 1)}
+              </span>
+              <span className="text-gray-400">
+                {row.map((byte) => toPrintableCharacter(byte)).join("")}
+              </span>
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+};
+
+const formatOffset = (offset: number) => offset.toString(16).padStart(8, "0");
+
+const toPrintableCharacter = (byte: number) =>
+  byte >= 0x20 && byte < 0x7f ? String.fromCharCode(byte) : ".";
@@ -0,0 +1,119 @@
+import type React from "react";
+import { useMemo } from "react";
+
+import { Spinner } from "../../../components";
+import { useSkinnedMeshGlb } from "../api";
+
+const GLB_MAGIC = 0x46546c67; // "glTF"
+const GLB_JSON_CHUNK_TYPE = 0x4e4f534a; // "JSON"
+
+type GltfJson = {
+  meshes?: { primitives?: unknown[] }[];
+  nodes?: unknown[];
+  skins?: { joints?: unknown[] }[];
+  materials?: unknown[];
+  animations?: unknown[];
+};
+
+type ModelSummary = {
+  meshCount: number;
+  primitiveCount: number;
+  nodeCount: number;
+  jointCount: number;
+  materialCount: number;
+  animationCount: number;
+};
+
+/** Read the JSON chunk of a glb, which always comes first */
+const readModelSummary = (glb: Uint8Array): ModelSummary | null => {
+  if (glb.byteLength < 20) {
+    return null;
+  }
+
+  const view = new DataView(glb.buffer, glb.byteOffset, glb.byteLength);
+  if (
+    view.getUint32(0, true) !== GLB_MAGIC ||
+    view.getUint32(16, true) !== GLB_JSON_CHUNK_TYPE
+  ) {
+    return null;
+  }
+
+  const jsonLength = view.getUint32(12, true);
+  if (20 + jsonLength > glb.byteLength) {
+    return null;
+  }
+
+  const gltf: GltfJson = JSON.parse(
+    new TextDecoder().decode(glb.subarray(20, 20 + jsonLength)),
+  );
+
+  return {
+    meshCount: gltf.meshes?.length ?? 0,
+    primitiveCount:
+      gltf.meshes?.reduce(
+        (count, mesh) => count + (mesh.primitives?.length ?? 0),
+        0,
+      ) ?? 0,
+    nodeCount: gltf.nodes?.length ?? 0,
+    jointCount:
+      gltf.skins?.reduce(
+        (count, skin) => count + (skin.joints?.length ?? 0),
+        0,
+      ) ?? 0,
+    materialCount: gltf.materials?.length ?? 0,
+    animationCount: gltf.animations?.length ?? 0,
+  };
+};
+
+export type ModelPreviewProps = {
+  wadId: string;
+  itemId: string;
+};
+
+export const ModelPreview: React.FC<ModelPreviewProps> = ({
+  wadId,
+  itemId,
+}) => {
+  const glb = useSkinnedMeshGlb({ wadId, itemId });
+
+  const summary = useMemo(
+    () => (glb.isSuccess ? readModelSummary(glb.data) : null),
+    [glb.isSuccess, glb.data],
+  );
+
+  if (glb.isLoading) {
+    return <Spinner />;
+  }
+
+  if (!glb.isSuccess || !summary) {
+    return null;
+  }
+
+  const rows: [string, number][] = [
+    ["Meshes", summary.meshCount],
+    ["Primitives", summary.primitiveCount],
+    ["Nodes", summary.nodeCount],
+    ["Joints", summary.jointCount],
+    ["Materials", summary.materialCount],
+    ["Animations", summary.animationCount],
+    ["Size (bytes)", glb.data.byteLength],
+  ];
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="border-b border-gray-600 p-1 text-sm text-gray-400">
+        glb
+      </div>
+      <table className="m-2 text-sm text-gray-50">
+        <tbody>
+          {rows.map(([label, value]) => (
+            <tr key={label}>
+              <td className="pr-4 text-gray-400">{label}</td>
+              <td className="font-mono">{value}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+};
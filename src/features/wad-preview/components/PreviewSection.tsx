@@ -1,10 +1,15 @@
 import { useEffect, useState } from "react";
+import { match } from "ts-pattern";
 import { useItemPreviewTypes } from "~/features/wad/api/getItemPreviewTypes";
 import { useWadContext } from "~/features/wad/providers";
 import { getImageBytes } from "../api";
+import { BinPreview } from "./BinPreview";
+import { HexPreview } from "./HexPreview";
+import { ModelPreview } from "./ModelPreview";
+import { TextPreview } from "./TextPreview";
 
 export const PreviewSection = () => {
-  const { wadId, currentPreviewItemId } = useWadContext();
+  const { wadId, currentPreviewItemId, currentPreviewType } = useWadContext();
 
   const { data: previewTypes } = useItemPreviewTypes({
     wadId,
@@ -12,13 +17,32 @@ export const PreviewSection = () => {
     enabled: !!currentPreviewItemId,
   });
 
-  const isImagePreviewType = previewTypes?.some((type) => type === "image");
+  // the preferred preview type comes first
+  const previewType =
+    currentPreviewType && previewTypes?.includes(currentPreviewType)
+      ? currentPreviewType
+      : previewTypes?.[0];
 
   return (
     <div className="h-full bg-gray-950">
-      {isImagePreviewType && currentPreviewItemId && (
-        <ImagePreview wadId={wadId} itemId={currentPreviewItemId} />
-      )}
+      {currentPreviewItemId &&
+        match(previewType)
+          .with("image", () => (
+            <ImagePreview wadId={wadId} itemId={currentPreviewItemId} />
+          ))
+          .with("model", () => (
+            <ModelPreview wadId={wadId} itemId={currentPreviewItemId} />
+          ))
+          .with("bin", () => (
+            <BinPreview wadId={wadId} itemId={currentPreviewItemId} />
+          ))
+          .with("text", () => (
+            <TextPreview wadId={wadId} itemId={currentPreviewItemId} />
+          ))
+          .with("hex", () => (
+            <HexPreview wadId={wadId} itemId={currentPreviewItemId} />
+          ))
+          .otherwise(() => null)}
     </div>
   );
 };
@@ -0,0 +1,40 @@
+import type React from "react";
+
+import { Spinner } from "../../../components";
+import { useTextPreview } from "../api";
+
+export type TextPreviewProps = {
+  wadId: string;
+  itemId: string;
+};
+
+export const TextPreview: React.FC<TextPreviewProps> = ({ wadId, itemId }) => {
+  const textPreview = useTextPreview({ wadId, itemId });
+
+  if (textPreview.isLoading) {
+    return <Spinner />;
+  }
+
+  if (!textPreview.isSuccess) {
+    return null;
+  }
+
+  const { content, language, encoding, isTruncated, totalSize } =
+    textPreview.data;
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="flex flex-row gap-4 border-b border-gray-600 p-1 text-sm text-gray-400">
+        <span>{language}</span>
+        <span>{encoding}</span>
+        {isTruncated && <span>truncated, {totalSize} bytes in total</span>}
+      </div>
+      <pre
+        className="overflow-auto p-2 font-mono text-sm text-gray-50"
+        data-language={language}
+      >
+        {content}
+      </pre>
+    </div>
+  );
+};
@@ -1 +1,5 @@
+export * from "./BinPreview";
+export * from "./HexPreview";
+export * from "./ModelPreview";
 export * from "./PreviewSection";
+export * from "./TextPreview";
@@ -1,3 +1,5 @@
 export * from "./api";
 export * from "./commands";
 export * from "./components";
+export * from "./queryKeys";
+export * from "./types";
@@ -0,0 +1,14 @@
+export const wadPreviewQueryKeys = {
+  binPreview: (wadId: string, itemId: string) =>
+    ["wad", wadId, "items", itemId, "bin_preview"] as const,
+  chunkBytesRange: (
+    wadId: string,
+    itemId: string,
+    offset: number,
+    length: number,
+  ) => ["wad", wadId, "items", itemId, "bytes", offset, length] as const,
+  skinnedMeshGlb: (wadId: string, itemId: string) =>
+    ["wad", wadId, "items", itemId, "skinned_mesh_glb"] as const,
+  textPreview: (wadId: string, itemId: string) =>
+    ["wad", wadId, "items", itemId, "text_preview"] as const,
+};
@@ -0,0 +1,80 @@
+export type BinPreviewResponse = {
+  isOverride: boolean;
+  version: number;
+  dependencies: string[];
+  entries: BinPreviewEntry[];
+};
+
+export type BinPreviewEntry = {
+  path: string;
+  className: string;
+  fields: BinPreviewField[];
+};
+
+export type BinPreviewField = {
+  name: string;
+  value: BinPreviewValue;
+};
+
+export type BinPreviewMapEntry = {
+  key: BinPreviewValue;
+  value: BinPreviewValue;
+};
+
+/**
+ * Hashes and links are resolved through the hashtables, unknown ones are hex
+ */
+export type BinPreviewValue =
+  | { kind: "none" }
+  | { kind: "bool" | "bitBool"; value: boolean }
+  | {
+      kind: "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "f32";
+      value: number;
+    }
+  | { kind: "vector2" | "vector3" | "vector4" | "matrix44"; value: number[] }
+  | { kind: "color"; value: [number, number, number, number] }
+  | {
+      kind: "string" | "hash" | "wadChunkLink" | "objectLink";
+      value: string;
+    }
+  | { kind: "container" | "unorderedContainer"; value: BinPreviewValue[] }
+  | {
+      kind: "struct" | "embedded";
+      value: { className: string; fields: BinPreviewField[] };
+    }
+  | { kind: "optional"; value: BinPreviewValue | null }
+  | { kind: "map"; value: BinPreviewMapEntry[] };
+
+export type ChunkBytesRangeResponse = {
+  offset: number;
+  bytes: number[];
+  /** Decompressed size of the whole chunk */
+  totalSize: number;
+};
+
+export type TextPreviewResponse = {
+  content: string;
+  language: TextLanguage;
+  encoding: TextEncoding;
+  /** Whether the content was cut off at the size limit */
+  isTruncated: boolean;
+  /** Size of the undecoded data */
+  totalSize: number;
+};
+
+export type TextLanguage =
+  | "plaintext"
+  | "lua"
+  | "json"
+  | "ini"
+  | "xml"
+  | "html"
+  | "css"
+  | "javascript"
+  | "glsl"
+  | "hlsl"
+  | "csv"
+  | "yaml"
+  | "python";
+
+export type TextEncoding = "utf8" | "utf16Le" | "utf16Be" | "latin1";
@@ -3,16 +3,20 @@ import type React from "react";
 import { useCallback, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { PiEyeDuotone } from "react-icons/pi";
-import { VscCopy } from "react-icons/vsc";
+import { VscCopy, VscEdit } from "react-icons/vsc";
 import { toast } from "react-toastify";
 import { Skeleton } from "~/components";
+import { WadHashtableOverrideDialog } from "~/features/hashtable";
 import { useItemPreviewTypes } from "~/features/wad";
 
 import { ContextMenu, Icon, Toast } from "../../../../../components";
 import { toastAutoClose } from "../../../../../utils/toast";
 import { useWadContext } from "../../../providers";
-import type { WadFileItem, WadItem } from "../../../types";
-import { isLeagueFilePreviewable } from "../../../utils";
+import type {
+  WadChunkPreviewType,
+  WadFileItem,
+  WadItem,
+} from "../../../types";
 import { ExtractItem } from "./ExtractItem";
 import { ExtractSelectedItem } from "./ExtractSelectedItem";
 
@@ -51,12 +55,7 @@ export const WadItemRowContextMenu: React.FC<WadItemRowContextMenuProps> = ({
           <Skeleton className="w-full" />
         )}
         {item.kind === "file" && previewTypesQuery.isSuccess && (
-          <ContextMenu.Sub>
-            <ContextMenu.SubTrigger>Preview as</ContextMenu.SubTrigger>
-            <ContextMenu.Portal>
-              <ContextMenu.SubContent></ContextMenu.SubContent>
-            </ContextMenu.Portal>
-          </ContextMenu.Sub>
+          <PreviewAsItem item={item} previewTypes={previewTypesQuery.data} />
         )}
         {item.kind === "file" && (
           <>
@@ -73,6 +72,7 @@ export const WadItemRowContextMenu: React.FC<WadItemRowContextMenuProps> = ({
         <ContextMenu.Separator />
         <CopyNameItem item={item} />
         <CopyPathItem item={item} />
+        {item.kind === "file" && <SetPathItem item={item} />}
       </ContextMenu.Content>
     </ContextMenu.Root>
   );
@@ -143,8 +143,8 @@ const PreviewItem = ({ item }: PreviewItemProps) => {
   }, [changeCurrentPreviewItemId, item.id]);
 
   return (
+    // every chunk can at least be shown as hex
     <ContextMenu.Item
-      disabled={!isLeagueFilePreviewable(item.extensionKind)}
       className="flex flex-row items-center gap-2"
       onClick={handleClick}
     >
@@ -153,3 +153,61 @@ const PreviewItem = ({ item }: PreviewItemProps) => {
     </ContextMenu.Item>
   );
 };
+
+type PreviewAsItemProps = {
+  item: WadFileItem;
+  previewTypes: WadChunkPreviewType[];
+};
+
+const PreviewAsItem = ({ item, previewTypes }: PreviewAsItemProps) => {
+  const { changeCurrentPreviewItemId } = useWadContext();
+  const [t] = useTranslation(["wad", "common"]);
+
+  return (
+    <ContextMenu.Sub>
+      <ContextMenu.SubTrigger>
+        {t("wad:contextMenu.previewAs")}
+      </ContextMenu.SubTrigger>
+      <ContextMenu.Portal>
+        <ContextMenu.SubContent>
+          {previewTypes.map((previewType) => (
+            <ContextMenu.Item
+              key={previewType}
+              onClick={() => changeCurrentPreviewItemId(item.id, previewType)}
+            >
+              {t(`wad:previewType.${previewType}`)}
+            </ContextMenu.Item>
+          ))}
+        </ContextMenu.SubContent>
+      </ContextMenu.Portal>
+    </ContextMenu.Sub>
+  );
+};
+
+type SetPathItemProps = {
+  item: WadFileItem;
+};
+
+const SetPathItem = ({ item }: SetPathItemProps) => {
+  const [t] = useTranslation("wad");
+  const [isDialogOpen, setIsDialogOpen] = useState(false);
+
+  return (
+    <>
+      <ContextMenu.Item
+        className="flex flex-row items-center gap-2"
+        onClick={() => setIsDialogOpen(true)}
+        onSelect={(e) => e.preventDefault()}
+      >
+        <Icon icon={VscEdit} size="md" />
+        {t("contextMenu.setPath")}
+      </ContextMenu.Item>
+      <WadHashtableOverrideDialog
+        open={isDialogOpen}
+        onOpenChange={setIsDialogOpen}
+        pathHash={item.chunkPathHash}
+        defaultPath={item.path}
+      />
+    </>
+  );
+};
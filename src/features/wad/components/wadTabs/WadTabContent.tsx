@@ -7,7 +7,11 @@ import { appRoutes } from "../../../../lib/router";
 import { composeUrlQuery } from "../../../../utils";
 import { useWadDirectoryPathComponents, useWadParentItems } from "../../api";
 import { WadContext, type WadContextState } from "../../providers";
-import type { WadItem, WadItemPathComponent } from "../../types";
+import type {
+  WadChunkPreviewType,
+  WadItem,
+  WadItemPathComponent,
+} from "../../types";
 import { WadSearchInput } from "../search";
 import { WadItemList } from "../wadItemList";
 import { ExtractAllButton } from "./ExtractAllButton";
@@ -81,12 +85,18 @@ const WadTabContent: React.FC<WadTabContentProps> = ({
   const [currentPreviewItemId, setCurrentPreviewItemId] = useState<
     string | null
   >(null);
+  const [currentPreviewType, setCurrentPreviewType] =
+    useState<WadChunkPreviewType | null>(null);
 
   const wadState = useMemo<WadContextState>(
     () => ({
       wadId,
       currentPreviewItemId,
-      changeCurrentPreviewItemId: (item) => setCurrentPreviewItemId(item),
+      currentPreviewType,
+      changeCurrentPreviewItemId: (item, previewType) => {
+        setCurrentPreviewItemId(item);
+        setCurrentPreviewType(previewType ?? null);
+      },
       navigate: (item) => {
         if (item) {
           navigate(
@@ -99,7 +109,7 @@ const WadTabContent: React.FC<WadTabContentProps> = ({
         }
       },
     }),
-    [currentPreviewItemId, navigate, wadId],
+    [currentPreviewItemId, currentPreviewType, navigate, wadId],
   );
 
   return (
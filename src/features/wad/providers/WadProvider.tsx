@@ -1,10 +1,17 @@
 import { createContext, useContext } from "react";
 
+import type { WadChunkPreviewType } from "../types";
+
 export type WadContextState = {
   wadId: string;
 
   currentPreviewItemId: string | null;
-  changeCurrentPreviewItemId: (itemId: string | null) => void;
+  /** Picked preview type, the item's preferred one is used if not set */
+  currentPreviewType: WadChunkPreviewType | null;
+  changeCurrentPreviewItemId: (
+    itemId: string | null,
+    previewType?: WadChunkPreviewType,
+  ) => void;
 
   navigate: (itemId: string | null) => void;
 };
@@ -12,6 +19,7 @@ export type WadContextState = {
 export const WadContext = createContext<WadContextState>({
   wadId: "",
   currentPreviewItemId: null,
+  currentPreviewType: null,
   changeCurrentPreviewItemId: () => {},
   navigate: () => {},
 });
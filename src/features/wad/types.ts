@@ -70,6 +70,8 @@ export type WadFileItem = {
   path: string;
   nameHash: number;
   pathHash: number;
+  /** Hex path hash of the chunk, the numeric hashes lose precision */
+  chunkPathHash: string;
 
   compressionKind: WadChunkCompressionKind;
   compressedSize: number;
@@ -133,4 +135,4 @@ export type WadChunkCompressionKind =
   | "zstd"
   | "zstd_multi";
 
-export type WadChunkPreviewType = "image";
+export type WadChunkPreviewType = "image" | "model" | "bin" | "hex" | "text";
@@ -0,0 +1,14 @@
+export const hashtable = {
+  overrides: {
+    title: "Hashtable overrides",
+    empty: "No hashes have been assigned a path",
+    remove: "Remove override",
+  },
+  overrideDialog: {
+    title: "Set path",
+    pathHash: "Hash",
+    path: "Path",
+    cancel: "Cancel",
+    submit: "Save",
+  },
+};
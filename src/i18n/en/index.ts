@@ -1,5 +1,6 @@
 import { common } from "./common";
 import { gameExplorer } from "./gameExplorer";
+import { hashtable } from "./hashtable";
 import { mountedWads } from "./mountedWads";
 import { route } from "./route";
 import { settings } from "./settings";
@@ -9,6 +10,7 @@ import { wad } from "./wad";
 export const en = {
   common,
   gameExplorer,
+  hashtable,
   mountedWads,
   route,
   settings,
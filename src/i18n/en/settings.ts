@@ -12,6 +12,10 @@ export const settings = {
   leagueDirectory: {
     label: "League of Legends game directory",
   },
+  wadHashtablesBaseUrl: {
+    label: "Wad hashtables download URL",
+    placeholder: "CommunityDragon",
+  },
   submit: {
     success: "Saved!",
   },
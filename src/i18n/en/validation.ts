@@ -1,3 +1,4 @@
 export const validation = {
   mustBeAPath: "Must be a valid path",
+  mustBeAUrl: "Must be a valid URL",
 };
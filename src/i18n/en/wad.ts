@@ -5,5 +5,14 @@ export const wad = {
     extract: "Extract",
     extractSelected: "Extract selected",
     preview: "Preview",
+    previewAs: "Preview as",
+    setPath: "Set path...",
+  },
+  previewType: {
+    image: "Image",
+    model: "Model",
+    bin: "Bin",
+    hex: "Hex",
+    text: "Text",
   },
 };
@@ -1,4 +1,5 @@
 import { Spinner } from "../components";
+import { WadHashtableOverrides } from "../features/hashtable";
 import {
   createSettigsFormData,
   SettingsForm,
@@ -17,6 +18,9 @@ export default function Settings() {
         {settings.isLoading && <Spinner />}
         {settings.isSuccess && <SettingsForm defaultValues={settings.data} />}
       </div>
+      <div className="w-full rounded border border-gray-600 bg-gray-900 p-8 shadow-inner">
+        <WadHashtableOverrides />
+      </div>
     </div>
   );
 }
@@ -8,8 +8,13 @@ export const wadHashtablesMissingExtensionSchema = z.object({
   kind: z.literal("wadHashtablesMissing"),
 });
 
+export const binHashtablesMissingExtensionSchema = z.object({
+  kind: z.literal("binHashtablesMissing"),
+});
+
 const apiErrorExtensions = {
   wadHashtablesMissing: wadHashtablesMissingExtensionSchema,
+  binHashtablesMissing: binHashtablesMissingExtensionSchema,
 };
 
 export type ApiErrorExtensionKind = keyof typeof apiErrorExtensions;
@@ -18,6 +23,7 @@ export type ApiErrorExtension = z.infer<typeof apiErrorExtensionSchema>;
 const apiErrorExtensionSchema = z.discriminatedUnion("kind", [
   emptyExtensionSchema,
   wadHashtablesMissingExtensionSchema,
+  binHashtablesMissingExtensionSchema,
 ]);
 
 export type ApiError = z.infer<typeof apiErrorSchema>;
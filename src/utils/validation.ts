@@ -16,3 +16,16 @@ export const pathStringSchema = z
         })),
   })
   .regex(windowsPathRegex);
+
+export const urlStringSchema = z
+  .string({
+    errorMap: (issue, ctx) =>
+      match(issue.code)
+        .with("invalid_string", () => ({
+          message: t("validation:mustBeAUrl"),
+        }))
+        .otherwise(() => ({
+          message: ctx.defaultError,
+        })),
+  })
+  .url();